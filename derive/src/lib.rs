@@ -0,0 +1,217 @@
+//! derive macros for gw2lib
+//!
+//! use them through `gw2lib::model` or `gw2lib_model`, not directly
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, Ident, LitBool, LitStr, Path, Type};
+
+/// implements `Endpoint` and, depending on the arguments, `EndpointWithId`,
+/// `BulkEndpoint` and `FixedEndpoint`
+///
+/// ```ignore
+/// #[derive(Endpoint)]
+/// #[endpoint(url = "v2/items", version = "2022-07-22T00:00:00.000Z", locale, bulk(all = false), id = "id")]
+/// pub struct Item {
+///     pub id: ItemId,
+/// }
+/// ```
+///
+/// ## Arguments
+/// - `url = "v2/items"` and `version = "…"`: required
+/// - `authenticated` and `locale`: flags, both default to off
+/// - `permissions(Account, Characters)`: variants of `Permissions`
+/// - `id = "core.name"`: field that holds the id, implements `EndpointWithId`
+/// - `id_type = "CharacterId"`: the id type, only needed if it can't be taken
+///   from the `id` field
+/// - `suffix = "core"`: requests single ids from `url/id/suffix`
+/// - `bulk` or `bulk(all = false)`: implements `BulkEndpoint`, `all` defaults
+///   to true
+/// - `fixed`: implements `FixedEndpoint`
+/// - `crate = "gw2lib::model"`: path to the model crate, for endpoints defined
+///   outside of it
+#[proc_macro_derive(Endpoint, attributes(endpoint))]
+pub fn derive_endpoint(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[derive(Default)]
+struct Args {
+    krate: Option<Path>,
+    url: Option<LitStr>,
+    version: Option<LitStr>,
+    authenticated: bool,
+    locale: bool,
+    permissions: Vec<Ident>,
+    id: Option<LitStr>,
+    id_type: Option<Type>,
+    suffix: Option<LitStr>,
+    bulk: Option<bool>,
+    fixed: bool,
+}
+
+fn parse_args(input: &DeriveInput) -> syn::Result<Args> {
+    let mut args = Args::default();
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("endpoint")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("crate") {
+                args.krate = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+            } else if meta.path.is_ident("url") {
+                args.url = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("version") {
+                args.version = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("authenticated") {
+                args.authenticated = true;
+            } else if meta.path.is_ident("locale") {
+                args.locale = true;
+            } else if meta.path.is_ident("permissions") {
+                meta.parse_nested_meta(|meta| {
+                    args.permissions.push(meta.path.require_ident()?.clone());
+                    Ok(())
+                })?;
+            } else if meta.path.is_ident("id") {
+                args.id = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("id_type") {
+                args.id_type = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+            } else if meta.path.is_ident("suffix") {
+                args.suffix = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("bulk") {
+                let mut all = true;
+                if !meta.input.is_empty() && !meta.input.peek(syn::Token![,]) {
+                    meta.parse_nested_meta(|meta| {
+                        if meta.path.is_ident("all") {
+                            all = meta.value()?.parse::<LitBool>()?.value;
+                            Ok(())
+                        } else {
+                            Err(meta.error("expected `all`"))
+                        }
+                    })?;
+                }
+                args.bulk = Some(all);
+            } else if meta.path.is_ident("fixed") {
+                args.fixed = true;
+            } else {
+                return Err(meta.error("unknown endpoint argument"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(args)
+}
+
+/// the type of the first field in `path`, if the struct has named fields
+fn field_type(input: &DeriveInput, path: &[Ident]) -> Option<Type> {
+    let Data::Struct(data) = &input.data else {
+        return None;
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return None;
+    };
+    if path.len() != 1 {
+        return None;
+    }
+    fields
+        .named
+        .iter()
+        .find(|f| f.ident.as_ref() == Some(&path[0]))
+        .map(|f| f.ty.clone())
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let args = parse_args(&input)?;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let krate = args
+        .krate
+        .unwrap_or_else(|| syn::parse_quote!(::gw2lib_model));
+
+    let url = args
+        .url
+        .ok_or_else(|| syn::Error::new(name.span(), "missing `url` argument"))?;
+    let version = args
+        .version
+        .ok_or_else(|| syn::Error::new(name.span(), "missing `version` argument"))?;
+    let authenticated = args.authenticated;
+    let locale = args.locale;
+    let permissions = (!args.permissions.is_empty()).then(|| {
+        let permissions = &args.permissions;
+        quote! {
+            const PERMISSIONS: &'static [#krate::authenticated::Permissions] =
+                &[#(#krate::authenticated::Permissions::#permissions),*];
+        }
+    });
+
+    let mut out = quote! {
+        impl #impl_generics #krate::Endpoint for #name #ty_generics #where_clause {
+            const AUTHENTICATED: bool = #authenticated;
+            const LOCALE: bool = #locale;
+            #permissions
+            const URL: &'static str = #url;
+            const VERSION: &'static str = #version;
+        }
+    };
+
+    let id = match &args.id {
+        Some(id) => Some(
+            id.value()
+                .split('.')
+                .map(|f| syn::parse_str::<Ident>(f).map_err(|e| syn::Error::new(id.span(), e)))
+                .collect::<syn::Result<Vec<_>>>()?,
+        ),
+        None => None,
+    };
+    let id_type = match (args.id_type, &args.id, &id) {
+        (Some(t), ..) => Some(t),
+        (None, Some(lit), Some(id)) => Some(field_type(&input, id).ok_or_else(|| {
+            syn::Error::new(lit.span(), "can't find the type of `id`, set `id_type`")
+        })?),
+        _ => None,
+    };
+
+    if let Some(id_type) = &id_type {
+        let format_url = args.suffix.map(|suffix| {
+            let format = LitStr::new(&format!("{{}}/{{}}/{}", suffix.value()), suffix.span());
+            quote! {
+                fn format_url(id: &str) -> String {
+                    format!(#format, <Self as #krate::Endpoint>::URL, id)
+                }
+            }
+        });
+        out.extend(quote! {
+            impl #impl_generics #krate::EndpointWithId for #name #ty_generics #where_clause {
+                type IdType = #id_type;
+                #format_url
+            }
+        });
+    } else if args.suffix.is_some() {
+        return Err(syn::Error::new(
+            name.span(),
+            "`suffix` needs `id` or `id_type`",
+        ));
+    }
+
+    if let Some(all) = args.bulk {
+        let id = id.ok_or_else(|| syn::Error::new(name.span(), "`bulk` needs `id`"))?;
+        out.extend(quote! {
+            impl #impl_generics #krate::BulkEndpoint for #name #ty_generics #where_clause {
+                const ALL: bool = #all;
+
+                fn id(&self) -> &Self::IdType {
+                    &self.#(#id).*
+                }
+            }
+        });
+    }
+
+    if args.fixed {
+        out.extend(quote! {
+            impl #impl_generics #krate::FixedEndpoint for #name #ty_generics #where_clause {}
+        });
+    }
+
+    Ok(out)
+}
@@ -1,11 +1,14 @@
 use std::{fmt::Display, hash::Hash};
 
 use chrono::Duration;
-use gw2lib_model::{BulkEndpoint, Endpoint, EndpointWithId, FixedEndpoint};
+use gw2lib_model::{
+    references::{References, Resolved},
+    BulkEndpoint, Endpoint, EndpointWithId, FixedEndpoint, Nested, PathId, QueryEndpoint,
+};
 use serde::de::DeserializeOwned;
 
 use super::requester::Requester as Req;
-use crate::{block::block, CachedRequest, Client, EndpointResult};
+use crate::{block::block, CachedRequest, Client, EndpointResult, JsonError, Priority};
 
 pub trait Requester<const AUTHENTICATED: bool, const FORCE: bool>:
     Req<AUTHENTICATED, FORCE>
@@ -16,21 +19,27 @@ pub trait Requester<const AUTHENTICATED: bool, const FORCE: bool>:
     #[doc(hidden)]
     fn cache_duration(&self) -> Duration;
 
+    #[doc(hidden)]
+    fn request_priority(&self) -> Priority;
+
+    #[doc(hidden)]
+    fn schema_version(&self) -> Option<&'static str>;
+
     /// overwrites the cache duration for all requests returned from this
     /// function ## Example
     /// ```
     /// use chrono::Duration;
     /// use gw2lib::{Client, Requester};
-    /// use gw2lib::model::items::Item;
+    /// use gw2lib::model::items::{Item, ItemId};
     /// use gw2lib::model::misc::build::Build;
     ///
     /// let client = Client::default();
     /// let cache_client = client.cached(Duration::seconds(5));
     /// // these requests get cached for 5s
     /// let build_id: Build = cache_client.get().unwrap();
-    /// let item: Item = cache_client.single(19993).unwrap();
+    /// let item: Item = cache_client.single(ItemId(19993)).unwrap();
     /// // normal caching
-    /// let other_item: Item = client.single(19721).unwrap();
+    /// let other_item: Item = client.single(ItemId(19721)).unwrap();
     fn cached(
         &self,
         cache_duration: Duration,
@@ -58,6 +67,53 @@ pub trait Requester<const AUTHENTICATED: bool, const FORCE: bool>:
         Req::forced(self)
     }
 
+    /// sets the priority for all requests returned from this function
+    ///
+    /// requests waiting for the rate limiter are served by priority, so
+    /// interactive lookups don't have to wait for background work. This only
+    /// orders the requests of this client, other clients sharing the rate
+    /// limiter are not affected.
+    /// ## Example
+    /// ```
+    /// use gw2lib::{Client, Priority, Requester};
+    ///
+    /// let client = Client::empty();
+    /// // requests made through this don't delay requests with a normal priority
+    /// let background = client.priority(Priority::Low);
+    /// ```
+    fn priority(
+        &self,
+        priority: Priority,
+    ) -> CachedRequest<'_, Self::Caching, Self::RateLimiting, Self::Connector, AUTHENTICATED, FORCE>
+    {
+        Req::priority(self, priority)
+    }
+
+    /// requests a schema version other than the one of the model for all
+    /// requests returned from this function
+    ///
+    /// responses are cached apart from those of other versions. The model
+    /// has to match the shape of that version, some endpoints have versioned
+    /// models for this.
+    /// ## Example
+    /// ```
+    /// use gw2lib::{model::authenticated::account::Account, Client, Requester};
+    ///
+    /// # fn example() -> Result<(), gw2lib::EndpointError> {
+    /// let client = Client::empty().api_key("<subtoken>");
+    /// let legacy = client.version("2019-12-19T00:00:00.000Z");
+    /// let account: Account = legacy.get()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn version(
+        &self,
+        version: &'static str,
+    ) -> CachedRequest<'_, Self::Caching, Self::RateLimiting, Self::Connector, AUTHENTICATED, FORCE>
+    {
+        Req::version(self, version)
+    }
+
     /// call the fixed endpoint
     fn get<T: DeserializeOwned + Clone + Send + Sync + FixedEndpoint + 'static>(
         &self,
@@ -65,6 +121,50 @@ pub trait Requester<const AUTHENTICATED: bool, const FORCE: bool>:
         block(Req::get(self))
     }
 
+    /// call an endpoint with query parameters
+    ///
+    /// responses are cached per set of parameters
+    /// ## Example
+    /// ```
+    /// use gw2lib::{
+    ///     model::items::recipes::{RecipeSearch, RecipeSearchQuery},
+    ///     Client, Requester,
+    /// };
+    ///
+    /// # fn example() -> Result<(), gw2lib::EndpointError> {
+    /// let client = Client::default();
+    /// let recipes: RecipeSearch = client.query(&RecipeSearchQuery::output(19721))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn query<T: DeserializeOwned + Clone + Send + Sync + QueryEndpoint + 'static>(
+        &self,
+        query: &T::Query,
+    ) -> EndpointResult<T> {
+        block(Req::query(self, query))
+    }
+
+    /// request any path of the api without a model type, e.g.
+    /// `v2/items?ids=19721,19993`
+    ///
+    /// the endpoint is looked up in the [`registry`](gw2lib_model::registry),
+    /// its version, authentication and permissions apply. Responses are
+    /// cached per path.
+    /// ## Example
+    /// ```
+    /// use gw2lib::{Client, Requester};
+    ///
+    /// # fn example() -> Result<(), gw2lib::EndpointError> {
+    /// let client = Client::default();
+    /// let items = client.raw("v2/items?ids=19721,19993")?;
+    /// let name = &items[0]["name"];
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn raw(&self, path: &str) -> EndpointResult<serde_json::Value> {
+        block(Req::raw(self, path))
+    }
+
     /// request a single item
     fn single<
         T: DeserializeOwned + Clone + Send + Sync + EndpointWithId<IdType = I> + 'static,
@@ -78,10 +178,13 @@ pub trait Requester<const AUTHENTICATED: bool, const FORCE: bool>:
 
     /// retrieves an item from cache
     /// ```
-    /// use gw2lib::{model::items::Item, Client, Requester};
+    /// use gw2lib::{
+    ///     model::items::{Item, ItemId},
+    ///     Client, Requester,
+    /// };
     ///
     /// let client = Client::default();
-    /// let from_cache: Option<Item> = client.try_get(&19721);
+    /// let from_cache: Option<Item> = client.try_get(&ItemId(19721));
     /// ```
     fn try_get<
         T: DeserializeOwned + Clone + Endpoint + Send + Sync + 'static,
@@ -103,6 +206,36 @@ pub trait Requester<const AUTHENTICATED: bool, const FORCE: bool>:
         block(Req::ids::<T, I>(self))
     }
 
+    /// request all available ids below `parent` of a nested endpoint
+    ///
+    /// `ids` only works for endpoints at the top of the hierarchy
+    /// ## Example
+    /// ```
+    /// use gw2lib::{
+    ///     model::misc::continents::{ContinentId, Floor, FloorPath, Region},
+    ///     Client, Requester,
+    /// };
+    ///
+    /// # fn example() -> Result<(), gw2lib::EndpointError> {
+    /// let client = Client::default();
+    /// let floors = client.nested_ids::<Floor, _, _>(ContinentId(1))?;
+    /// let regions = client.nested_ids::<Region, _, _>(floors[0])?;
+    /// let region: Region = client.single(regions[0])?;
+    /// let floor: Floor = client.single(FloorPath::new(1, 1))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn nested_ids<
+        T: DeserializeOwned + EndpointWithId<IdType = Nested<P, I>> + Clone + Send + Sync + 'static,
+        P: PathId + Hash + Clone + Send + Sync + 'static,
+        I: DeserializeOwned + Hash + Clone + Send + Sync + 'static,
+    >(
+        &self,
+        parent: P,
+    ) -> EndpointResult<Vec<Nested<P, I>>> {
+        block(Req::nested_ids::<T, P, I>(self, parent))
+    }
+
     /// request multiple ids at once
     fn many<
         T: DeserializeOwned
@@ -120,6 +253,29 @@ pub trait Requester<const AUTHENTICATED: bool, const FORCE: bool>:
         block(Req::many(self, ids))
     }
 
+    /// request multiple ids at once, skipping entities that fail to
+    /// deserialize
+    ///
+    /// every entity is deserialized on its own, so an unknown variant only
+    /// affects that entity instead of the whole batch. The successfully
+    /// parsed entities are returned and cached as usual, the failures are
+    /// returned alongside them.
+    fn many_lenient<
+        T: DeserializeOwned
+            + EndpointWithId<IdType = I>
+            + BulkEndpoint
+            + Clone
+            + Send
+            + Sync
+            + 'static,
+        I: Display + DeserializeOwned + Hash + Clone + Eq + Send + Sync + 'static,
+    >(
+        &self,
+        ids: Vec<I>,
+    ) -> EndpointResult<(Vec<T>, Vec<JsonError>)> {
+        block(Req::many_lenient(self, ids))
+    }
+
     /// requests a page of items and returns the number of total items across
     /// all pages
     fn page<
@@ -162,6 +318,25 @@ pub trait Requester<const AUTHENTICATED: bool, const FORCE: bool>:
         block(Req::all(self))
     }
 
+    /// requests all items like [`Self::all`], skipping entities that fail to
+    /// deserialize
+    ///
+    /// see [`Self::many_lenient`]
+    fn all_lenient<
+        T: DeserializeOwned
+            + EndpointWithId<IdType = I>
+            + BulkEndpoint
+            + Clone
+            + Send
+            + Sync
+            + 'static,
+        I: Display + DeserializeOwned + Hash + Clone + Send + Sync + Eq + 'static,
+    >(
+        &self,
+    ) -> EndpointResult<(Vec<T>, Vec<JsonError>)> {
+        block(Req::all_lenient(self))
+    }
+
     /// Gets all items by querying ids=all
     ///
     /// use [`Self::all`] to use the most efficient way to request all items
@@ -215,6 +390,28 @@ pub trait Requester<const AUTHENTICATED: bool, const FORCE: bool>:
     ) -> EndpointResult<Vec<T>> {
         block(Req::get_all_by_requesting_ids(self))
     }
+
+    /// requests every entity `value` refers to by id
+    ///
+    /// the ids are batched per endpoint and requested with [`Self::many`], so
+    /// cached entities are not requested again
+    /// ## Example
+    /// ```
+    /// use gw2lib::{model::authenticated::characters::Equipment, Client, Requester};
+    ///
+    /// # fn example() -> Result<(), gw2lib::EndpointError> {
+    /// let client = Client::empty().api_key("<subtoken>");
+    /// let equipment: Equipment = client.single("My Character".to_string())?;
+    /// let resolved = client.resolve(&equipment)?;
+    /// for equip in &equipment.equipment {
+    ///     let item = &resolved.items[&equip.id];
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn resolve<T: References + Sync + ?Sized>(&self, value: &T) -> EndpointResult<Resolved> {
+        block(Req::resolve(self, value))
+    }
 }
 
 impl<T: Req<AUTHENTICATED, FORCE>, const AUTHENTICATED: bool, const FORCE: bool>
@@ -227,4 +424,12 @@ impl<T: Req<AUTHENTICATED, FORCE>, const AUTHENTICATED: bool, const FORCE: bool>
     fn cache_duration(&self) -> Duration {
         Req::cache_duration(self)
     }
+
+    fn request_priority(&self) -> Priority {
+        Req::request_priority(self)
+    }
+
+    fn schema_version(&self) -> Option<&'static str> {
+        Req::schema_version(self)
+    }
 }
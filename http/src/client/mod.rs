@@ -1,4 +1,6 @@
+mod builder;
 mod requester;
+mod scheduler;
 use core::default::Default;
 use std::{
     any::{Any, TypeId},
@@ -7,26 +9,43 @@ use std::{
 
 #[cfg(feature = "blocking")]
 pub use blocking::Requester;
+pub use builder::*;
 #[cfg(not(feature = "blocking"))]
 pub use requester::Requester;
+pub use scheduler::Priority;
+use scheduler::Scheduler;
 #[cfg(feature = "blocking")]
 mod blocking;
 
 use chrono::Duration;
 use fxhash::FxHashMap;
-use gw2lib_model::Language;
-use hyper::client::{connect::Connect, HttpConnector};
+use gw2lib_model::{authenticated::Permissions, Language};
+use hyper::{
+    client::{connect::Connect, HttpConnector},
+    header::{HeaderName, HeaderValue, USER_AGENT},
+    HeaderMap, Request,
+};
 use hyper_rustls::HttpsConnector;
 use static_init::dynamic;
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, OnceCell, Semaphore};
 
 use crate::{
-    cache::CleanupCache, BucketRateLimiter, Cache, InMemoryCache, NoopCache, NoopRateLimiter,
+    cache::CleanupCache,
+    connector::{Proxy, ProxyConnector},
+    metrics::Metrics,
+    rate_limit::RateLimitStatus,
+    BucketRateLimiter, Cache, EndpointResult, InMemoryCache, NoopCache, NoopRateLimiter,
     RateLimiter,
 };
 
 pub(crate) type Inflight = Arc<Mutex<FxHashMap<(TypeId, u64), Box<dyn Any + Send>>>>;
 
+/// called with every request right before it is sent
+pub type RequestHook = Arc<dyn Fn(&mut Request<hyper::Body>) + Send + Sync>;
+
+/// the `User-Agent` header sent if none is configured
+pub const DEFAULT_USER_AGENT: &str = concat!("gw2lib/", env!("CARGO_PKG_VERSION"));
+
 pub struct Client<
     C: Cache,
     R: RateLimiter,
@@ -40,6 +59,17 @@ pub struct Client<
     cache: Arc<Mutex<C>>,
     inflight: Inflight,
     rate_limiter: Arc<Mutex<R>>,
+    headers: HeaderMap,
+    request_hook: Option<RequestHook>,
+    metrics: Option<Arc<dyn Metrics>>,
+    error_body: bool,
+    timeout: Option<std::time::Duration>,
+    /// kept to rebuild the connector in [`Client::host`] and friends
+    connect_timeout: Option<std::time::Duration>,
+    concurrency: Option<Arc<Semaphore>>,
+    scheduler: Arc<Scheduler>,
+    /// permissions of the api key, requested on first use
+    permissions: Arc<OnceCell<Vec<Permissions>>>,
 }
 
 impl Client<NoopCache, NoopRateLimiter, HttpsConnector<HttpConnector>, false> {
@@ -50,7 +80,7 @@ impl Client<NoopCache, NoopRateLimiter, HttpsConnector<HttpConnector>, false> {
     /// If you want to use a default cache and rate limiter, use
     /// [`Client::default`].
     pub fn empty() -> Self {
-        let client = create_client();
+        let client = create_client(None);
         let rate_limiter = Arc::new(Mutex::new(NoopRateLimiter {}));
         Self {
             host: "https://api.guildwars2.com".to_string(),
@@ -60,13 +90,29 @@ impl Client<NoopCache, NoopRateLimiter, HttpsConnector<HttpConnector>, false> {
             cache: Arc::new(Mutex::new(NoopCache {})),
             inflight: Default::default(),
             rate_limiter,
+            headers: default_headers(),
+            request_hook: None,
+            metrics: None,
+            error_body: false,
+            timeout: None,
+            connect_timeout: None,
+            concurrency: None,
+            scheduler: Default::default(),
+            permissions: Default::default(),
         }
     }
 }
 
+impl Client<InMemoryCache, BucketRateLimiter, HttpsConnector<HttpConnector>, false> {
+    /// creates a [`ClientBuilder`] to configure and validate a new client
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::new()
+    }
+}
+
 impl Default for Client<InMemoryCache, BucketRateLimiter, HttpsConnector<HttpConnector>, false> {
     fn default() -> Self {
-        let client = create_client();
+        let client = create_client(None);
         let rate_limiter = Arc::new(Mutex::new(BucketRateLimiter::default()));
         let cache = Arc::new(Mutex::new(InMemoryCache::default()));
         periodically_cleanup_cache(cache.clone());
@@ -78,6 +124,15 @@ impl Default for Client<InMemoryCache, BucketRateLimiter, HttpsConnector<HttpCon
             cache,
             inflight: Default::default(),
             rate_limiter,
+            headers: default_headers(),
+            request_hook: None,
+            metrics: None,
+            error_body: false,
+            timeout: None,
+            connect_timeout: None,
+            concurrency: None,
+            scheduler: Default::default(),
+            permissions: Default::default(),
         }
     }
 }
@@ -98,7 +153,7 @@ impl<
         self,
         host: impl Into<String>,
     ) -> Client<C, R, HttpsConnector<HttpConnector>, AUTHENTICATED> {
-        let client = create_client();
+        let client = create_client(self.connect_timeout);
         Client {
             host: host.into(),
             language: self.language,
@@ -107,6 +162,15 @@ impl<
             cache: self.cache,
             inflight: self.inflight,
             rate_limiter: self.rate_limiter,
+            headers: self.headers,
+            request_hook: self.request_hook,
+            metrics: self.metrics,
+            error_body: self.error_body,
+            timeout: self.timeout,
+            connect_timeout: self.connect_timeout,
+            concurrency: self.concurrency,
+            scheduler: self.scheduler,
+            permissions: self.permissions,
         }
     }
 
@@ -114,7 +178,9 @@ impl<
     ///
     /// for https hosts use [`Client::host`]
     pub fn host_http(self, host: impl Into<String>) -> Client<C, R, HttpConnector, AUTHENTICATED> {
-        let client = hyper::Client::new();
+        let mut http = HttpConnector::new();
+        http.set_connect_timeout(self.connect_timeout);
+        let client = hyper::Client::builder().build(http);
         Client {
             host: host.into(),
             language: self.language,
@@ -123,6 +189,52 @@ impl<
             cache: self.cache,
             inflight: self.inflight,
             rate_limiter: self.rate_limiter,
+            headers: self.headers,
+            request_hook: self.request_hook,
+            metrics: self.metrics,
+            error_body: self.error_body,
+            timeout: self.timeout,
+            connect_timeout: self.connect_timeout,
+            concurrency: self.concurrency,
+            scheduler: self.scheduler,
+            permissions: self.permissions,
+        }
+    }
+
+    /// routes all connections through the given proxy
+    ///
+    /// [`Client::host`] and [`Client::host_http`] replace the connector, so
+    /// call this afterwards. All three keep the connect timeout of
+    /// [`ClientBuilder::connect_timeout`].
+    /// ## Example
+    /// ```
+    /// use gw2lib::{connector::Proxy, Client};
+    ///
+    /// let client = Client::empty().proxy(Proxy::socks5("localhost", 1080));
+    /// ```
+    pub fn proxy(
+        self,
+        proxy: Proxy,
+    ) -> Client<C, R, HttpsConnector<ProxyConnector>, AUTHENTICATED> {
+        let https_only = !self.host.starts_with("http://");
+        let client = create_proxy_client(https_only, Some(proxy), self.connect_timeout);
+        Client {
+            host: self.host,
+            language: self.language,
+            client,
+            api_key: self.api_key,
+            cache: self.cache,
+            inflight: self.inflight,
+            rate_limiter: self.rate_limiter,
+            headers: self.headers,
+            request_hook: self.request_hook,
+            metrics: self.metrics,
+            error_body: self.error_body,
+            timeout: self.timeout,
+            connect_timeout: self.connect_timeout,
+            concurrency: self.concurrency,
+            scheduler: self.scheduler,
+            permissions: self.permissions,
         }
     }
 
@@ -131,7 +243,63 @@ impl<
         self.language = language.into();
     }
 
+    /// sets the `User-Agent` header
+    ///
+    /// ArenaNet asks tool authors to identify themselves, default is
+    /// [`DEFAULT_USER_AGENT`]
+    pub fn user_agent(&mut self, user_agent: HeaderValue) {
+        self.headers.insert(USER_AGENT, user_agent);
+    }
+
+    /// adds a header that is sent with every request
+    pub fn header(&mut self, name: HeaderName, value: HeaderValue) {
+        self.headers.insert(name, value);
+    }
+
+    /// returns the headers that are sent with every request
+    pub fn headers_mut(&mut self) -> &mut HeaderMap {
+        &mut self.headers
+    }
+
+    /// sets a function that gets called with every request right before it
+    /// is sent
+    /// ## Example
+    /// ```
+    /// use gw2lib::Client;
+    ///
+    /// let mut client = Client::empty();
+    /// client.request_hook(|request| {
+    ///     request
+    ///         .headers_mut()
+    ///         .insert("X-Trace-Id", "1234".parse().unwrap());
+    /// });
+    /// ```
+    pub fn request_hook(
+        &mut self,
+        hook: impl Fn(&mut Request<hyper::Body>) + Send + Sync + 'static,
+    ) {
+        self.request_hook = Some(Arc::new(hook));
+    }
+
+    /// reports measurements of every request to `metrics`, see [`Metrics`]
+    pub fn metrics(&mut self, metrics: Arc<dyn Metrics>) {
+        self.metrics = Some(metrics);
+    }
+
+    /// keeps the raw json of the failing entity in deserialization errors,
+    /// see [`JsonError::body`](crate::JsonError::body)
+    ///
+    /// default is `false`, responses can be large
+    pub fn error_body(&mut self, enabled: bool) {
+        self.error_body = enabled;
+    }
+
     /// sets a new api key
+    ///
+    /// the permissions of the key are requested from `v2/tokeninfo` before the
+    /// first request that needs any, requests to endpoints the key has no
+    /// access to fail with
+    /// [`EndpointError::MissingPermission`](crate::EndpointError::MissingPermission)
     /// ### Warning
     /// this wipes the cache for all authenticated endpoints to prevent leaking
     /// account specific information
@@ -141,6 +309,11 @@ impl<
     }
 
     /// sets a new api key
+    ///
+    /// the permissions of the key are requested from `v2/tokeninfo` before the
+    /// first request that needs any, requests to endpoints the key has no
+    /// access to fail with
+    /// [`EndpointError::MissingPermission`](crate::EndpointError::MissingPermission)
     /// ### Warning
     /// this wipes the cache for all authenticated endpoints to prevent leaking
     /// account specific information
@@ -170,6 +343,15 @@ impl<
             cache,
             inflight: self.inflight,
             rate_limiter: self.rate_limiter,
+            headers: self.headers,
+            request_hook: self.request_hook,
+            metrics: self.metrics,
+            error_body: self.error_body,
+            timeout: self.timeout,
+            connect_timeout: self.connect_timeout,
+            concurrency: self.concurrency,
+            scheduler: self.scheduler,
+            permissions: self.permissions,
         }
     }
 
@@ -198,10 +380,56 @@ impl<
             cache: self.cache,
             inflight: self.inflight,
             rate_limiter,
+            headers: self.headers,
+            request_hook: self.request_hook,
+            metrics: self.metrics,
+            error_body: self.error_body,
+            timeout: self.timeout,
+            connect_timeout: self.connect_timeout,
+            concurrency: self.concurrency,
+            scheduler: self.scheduler,
+            permissions: self.permissions,
         }
     }
 }
 
+/// inspecting client
+impl<
+        C: Cache,
+        R: RateLimiter + Sync,
+        Conn: Connect + Clone + Send + Sync + 'static,
+        const AUTHENTICATED: bool,
+    > Client<C, R, Conn, AUTHENTICATED>
+{
+    /// reports the remaining budget of the rate limiter and how long requests
+    /// had to wait for it
+    #[cfg(not(feature = "blocking"))]
+    pub async fn rate_limit_status(&self) -> EndpointResult<RateLimitStatus> {
+        self.rate_limiter.lock().await.status().await
+    }
+
+    /// reports the remaining budget of the rate limiter and how long requests
+    /// had to wait for it
+    /// ## Example
+    /// ```
+    /// use gw2lib::Client;
+    ///
+    /// let client = Client::default();
+    /// let status = client.rate_limit_status().unwrap();
+    /// println!("{} of {} requests left", status.available, status.capacity);
+    /// ```
+    #[cfg(feature = "blocking")]
+    pub fn rate_limit_status(&self) -> EndpointResult<RateLimitStatus> {
+        crate::block::block(async { self.rate_limiter.lock().await.status().await })
+    }
+
+    /// number of requests of this client waiting for their turn at the rate
+    /// limiter, see [`Priority`]
+    pub fn queued_requests(&self) -> usize {
+        self.scheduler.queued()
+    }
+}
+
 impl<
         C: Cache + Send,
         R: RateLimiter + Sync,
@@ -220,6 +448,14 @@ impl<
     fn cache_duration(&self) -> Duration {
         Duration::zero()
     }
+
+    fn request_priority(&self) -> Priority {
+        Priority::Normal
+    }
+
+    fn schema_version(&self) -> Option<&'static str> {
+        None
+    }
 }
 
 pub struct CachedRequest<
@@ -232,6 +468,8 @@ pub struct CachedRequest<
 > {
     client: &'client Client<C, R, Conn, AUTHENTICATED>,
     cache_duration: Duration,
+    priority: Priority,
+    version: Option<&'static str>,
 }
 
 impl<
@@ -254,14 +492,53 @@ impl<
     fn cache_duration(&self) -> Duration {
         self.cache_duration
     }
+
+    fn request_priority(&self) -> Priority {
+        self.priority
+    }
+
+    fn schema_version(&self) -> Option<&'static str> {
+        self.version
+    }
+}
+
+pub(crate) fn default_headers() -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert(USER_AGENT, HeaderValue::from_static(DEFAULT_USER_AGENT));
+    headers
 }
 
-fn create_client() -> hyper::Client<HttpsConnector<HttpConnector>, hyper::Body> {
+fn create_client(
+    connect_timeout: Option<std::time::Duration>,
+) -> hyper::Client<HttpsConnector<HttpConnector>, hyper::Body> {
+    let mut http = HttpConnector::new();
+    http.set_connect_timeout(connect_timeout);
+    // like `build`, let https urls through to the tls layer
+    http.enforce_http(false);
     let https = hyper_rustls::HttpsConnectorBuilder::new()
         .with_native_roots()
         .https_only()
         .enable_http1()
-        .build();
+        .wrap_connector(http);
+    hyper::Client::builder().build(https)
+}
+
+pub(crate) fn create_proxy_client(
+    https_only: bool,
+    proxy: Option<Proxy>,
+    connect_timeout: Option<std::time::Duration>,
+) -> hyper::Client<HttpsConnector<ProxyConnector>, hyper::Body> {
+    let mut http = HttpConnector::new();
+    http.set_connect_timeout(connect_timeout);
+    let builder = hyper_rustls::HttpsConnectorBuilder::new().with_native_roots();
+    let builder = if https_only {
+        builder.https_only()
+    } else {
+        builder.https_or_http()
+    };
+    let https = builder
+        .enable_http1()
+        .wrap_connector(ProxyConnector::new(http, proxy));
     hyper::Client::builder().build(https)
 }
 
@@ -283,10 +560,22 @@ async fn set_api_key<
         cache: client.cache,
         inflight: client.inflight,
         rate_limiter: client.rate_limiter,
+        headers: client.headers,
+        request_hook: client.request_hook,
+        metrics: client.metrics,
+        error_body: client.error_body,
+        timeout: client.timeout,
+        connect_timeout: client.connect_timeout,
+        concurrency: client.concurrency,
+        scheduler: client.scheduler,
+        // the permissions belong to the previous key
+        permissions: Default::default(),
     }
 }
 
-fn periodically_cleanup_cache(cache: Arc<Mutex<dyn CleanupCache + Send + Sync + 'static>>) {
+pub(crate) fn periodically_cleanup_cache(
+    cache: Arc<Mutex<dyn CleanupCache + Send + Sync + 'static>>,
+) {
     #[dynamic]
     static CACHES: Mutex<Vec<Weak<Mutex<dyn CleanupCache + Send + Sync>>>> =
         Mutex::new(Vec::with_capacity(1));
@@ -1,8 +1,8 @@
 use std::{
     any::TypeId,
-    collections::hash_map::Entry,
+    collections::{hash_map::Entry, BTreeMap, BTreeSet, HashMap},
     fmt::Display,
-    hash::Hash,
+    hash::{Hash, Hasher},
     ops::Deref,
     str::FromStr,
     sync::{Arc, Weak},
@@ -12,20 +12,27 @@ use async_trait::async_trait;
 use chrono::{Duration, NaiveDateTime, Utc};
 use either::Either;
 use futures::{stream::FuturesUnordered, StreamExt};
-use fxhash::FxHashMap;
+use fxhash::{FxHashMap, FxHasher};
 use gw2lib_model::{
-    BulkEndpoint, Endpoint, EndpointWithId, FixedEndpoint, Language, PagedEndpoint,
+    authenticated::{Permissions, Tokeninfo},
+    items::{recipes::Recipe, Item},
+    misc::{colors::Color, currencies::Currency, worlds::World},
+    references::{ReferenceSet, References, Resolved},
+    registry::{self, EndpointInfo},
+    BulkEndpoint, Endpoint, EndpointWithId, FixedEndpoint, Language, Nested, PagedEndpoint, PathId,
+    QueryEndpoint,
 };
-use hyper::{body::Buf, client::connect::Connect, Request, Response, Uri};
+use hyper::{body::Bytes, client::connect::Connect, Request, Response, Uri};
 use serde::de::DeserializeOwned;
+use serde_json::value::RawValue;
 use tokio::sync::{
-    broadcast::{self, Receiver, Sender},
+    broadcast::{self, error::RecvError, Receiver, Sender},
     Mutex,
 };
 
 use crate::{
-    cache::hash, ApiError, Cache, CachedRequest, Client, EndpointError, EndpointResult, Inflight,
-    RateLimiter,
+    cache::hash, metrics::StatusClass, ApiError, Cache, CachedRequest, Client, EndpointError,
+    EndpointResult, Inflight, JsonError, Priority, RateLimiter,
 };
 
 #[async_trait]
@@ -40,21 +47,27 @@ pub trait Requester<const AUTHENTICATED: bool, const FORCE: bool>: Sized + Sync
     #[doc(hidden)]
     fn cache_duration(&self) -> Duration;
 
+    #[doc(hidden)]
+    fn request_priority(&self) -> Priority;
+
+    #[doc(hidden)]
+    fn schema_version(&self) -> Option<&'static str>;
+
     /// overwrites the cache duration for all requests returned from this
     /// function ## Example
     /// ```
     /// use chrono::Duration;
     /// use gw2lib::{Client, Requester};
-    /// use gw2lib::model::items::Item;
+    /// use gw2lib::model::items::{Item, ItemId};
     /// use gw2lib::model::misc::build::Build;
     ///
     /// let client = Client::default();
     /// let cache_client = client.cached(Duration::seconds(5));
     /// // these requests get cached for 5s
     /// let build_id: Build = cache_client.get().unwrap();
-    /// let item: Item = cache_client.single(19993).unwrap();
+    /// let item: Item = cache_client.single(ItemId(19993)).unwrap();
     /// // normal caching
-    /// let other_item: Item = client.single(19721).unwrap();
+    /// let other_item: Item = client.single(ItemId(19721)).unwrap();
     fn cached(
         &self,
         cache_duration: Duration,
@@ -63,6 +76,8 @@ pub trait Requester<const AUTHENTICATED: bool, const FORCE: bool>: Sized + Sync
         CachedRequest {
             client: self.client(),
             cache_duration,
+            priority: self.request_priority(),
+            version: self.schema_version(),
         }
     }
 
@@ -85,6 +100,54 @@ pub trait Requester<const AUTHENTICATED: bool, const FORCE: bool>: Sized + Sync
         CachedRequest {
             client: self.client(),
             cache_duration: Duration::zero(),
+            priority: self.request_priority(),
+            version: self.schema_version(),
+        }
+    }
+
+    /// sets the priority for all requests returned from this function
+    ///
+    /// requests waiting for the rate limiter are served by priority, so
+    /// interactive lookups don't have to wait for background work. This only
+    /// orders the requests of this client, other clients sharing the rate
+    /// limiter are not affected.
+    /// ## Example
+    /// ```
+    /// use gw2lib::{Client, Priority, Requester};
+    ///
+    /// let client = Client::empty();
+    /// // requests made through this don't delay requests with a normal priority
+    /// let background = client.priority(Priority::Low);
+    /// ```
+    fn priority(
+        &self,
+        priority: Priority,
+    ) -> CachedRequest<'_, Self::Caching, Self::RateLimiting, Self::Connector, AUTHENTICATED, FORCE>
+    {
+        CachedRequest {
+            client: self.client(),
+            cache_duration: self.cache_duration(),
+            priority,
+            version: self.schema_version(),
+        }
+    }
+
+    /// requests a schema version other than the one of the model for all
+    /// requests returned from this function
+    ///
+    /// responses are cached apart from those of other versions. The model
+    /// has to match the shape of that version, some endpoints have versioned
+    /// models for this.
+    fn version(
+        &self,
+        version: &'static str,
+    ) -> CachedRequest<'_, Self::Caching, Self::RateLimiting, Self::Connector, AUTHENTICATED, FORCE>
+    {
+        CachedRequest {
+            client: self.client(),
+            cache_duration: self.cache_duration(),
+            priority: self.request_priority(),
+            version: Some(version),
         }
     }
 
@@ -95,7 +158,60 @@ pub trait Requester<const AUTHENTICATED: bool, const FORCE: bool>: Sized + Sync
         get_or_ids::<T, T, Self, AUTHENTICATED, FORCE>(self).await
     }
 
+    /// call an endpoint with query parameters
+    ///
+    /// responses are cached per set of parameters
+    async fn query<T: DeserializeOwned + Clone + Send + Sync + QueryEndpoint + 'static>(
+        &self,
+        query: &T::Query,
+    ) -> EndpointResult<T> {
+        let queries = serde_urlencoded::to_string(query).map_err(EndpointError::InvalidQuery)?;
+        get_cached::<T, T, T::Query, Self, AUTHENTICATED, FORCE>(self, query, T::URL, Some(queries))
+            .await
+    }
+
+    /// request any path of the api without a model type, e.g.
+    /// `v2/items?ids=19721,19993`
+    ///
+    /// the endpoint is looked up in the [`registry`](gw2lib_model::registry),
+    /// its version, authentication and permissions apply. Responses are
+    /// cached per path.
+    async fn raw(&self, path: &str) -> EndpointResult<serde_json::Value> {
+        let info =
+            registry::find(path).ok_or_else(|| EndpointError::UnknownEndpoint(path.to_string()))?;
+        match (info.authenticated, info.locale) {
+            (false, false) => {
+                get_raw::<RawEndpoint<false, false>, _, _, _>(self, &info, path).await
+            }
+            (false, true) => get_raw::<RawEndpoint<false, true>, _, _, _>(self, &info, path).await,
+            (true, false) => get_raw::<RawEndpoint<true, false>, _, _, _>(self, &info, path).await,
+            (true, true) => get_raw::<RawEndpoint<true, true>, _, _, _>(self, &info, path).await,
+        }
+    }
+
     /// request a single item
+    ///
+    /// the id has to belong to the endpoint, ids of other endpoints don't
+    /// compile
+    /// ## Example
+    /// ```no_run
+    /// use gw2lib::{
+    ///     model::items::{Item, ItemId},
+    ///     Client, Requester,
+    /// };
+    ///
+    /// let client = Client::default();
+    /// let item = client.single::<Item, _>(ItemId(19721));
+    /// ```
+    /// ```compile_fail
+    /// use gw2lib::{
+    ///     model::items::{recipes::RecipeId, Item},
+    ///     Client, Requester,
+    /// };
+    ///
+    /// let client = Client::default();
+    /// let item = client.single::<Item, _>(RecipeId(7314));
+    /// ```
     async fn single<
         T: DeserializeOwned + Clone + Send + Sync + EndpointWithId<IdType = I> + 'static,
         I: Display + DeserializeOwned + Hash + Send + Sync + Clone + 'static,
@@ -110,7 +226,9 @@ pub trait Requester<const AUTHENTICATED: bool, const FORCE: bool>: Sized + Sync
         }
 
         let tx = loop {
-            let either = check_inflight::<T, I, T>(&self.client().inflight, &id, lang).await;
+            let inflight = &self.client().inflight;
+            let either =
+                check_inflight::<T, I, T>(inflight, &id, lang, self.schema_version()).await;
             match either {
                 Some(Either::Left(mut rx)) => return rx.recv().await.map_err(Into::into),
                 Some(Either::Right(tx)) => break tx,
@@ -122,15 +240,28 @@ pub trait Requester<const AUTHENTICATED: bool, const FORCE: bool>: Sized + Sync
             }
         };
 
+        let url = T::format_url(urlencoding::encode(&id.to_string()).as_ref());
         let request = build_request::<T, String, Self, AUTHENTICATED, FORCE>(
             self,
-            &T::format_url(urlencoding::encode(&id.to_string()).as_ref()),
+            &fill_path(&url, &T::parents(&id)),
             None,
         )?;
 
-        let response = exec_req::<Self, AUTHENTICATED, FORCE>(self, request).await?;
-        let result =
-            cache_response::<I, T, T, Self, AUTHENTICATED, FORCE>(self, &id, response).await?;
+        let response = exec_req::<T, Self, AUTHENTICATED, FORCE>(self, request).await?;
+        let (expires, mut result): (_, T) =
+            parse_response::<T, _, _, AUTHENTICATED, FORCE>(self, response).await?;
+        result.set_parents(&id);
+        {
+            let mut cache = self.client().cache.lock().await;
+            cache_insert::<T, I, T, Self, AUTHENTICATED, FORCE>(
+                self,
+                &mut cache,
+                &id,
+                result.clone(),
+                expires,
+            )
+            .await;
+        }
         // ignoring the error is fine here
         // the receiving side will check the cache if nothing got sent
         let _ = tx.lock().await.send(result.clone());
@@ -140,10 +271,13 @@ pub trait Requester<const AUTHENTICATED: bool, const FORCE: bool>: Sized + Sync
 
     /// retrieves an item from cache
     /// ```
-    /// use gw2lib::{model::items::Item, Client, Requester};
+    /// use gw2lib::{
+    ///     model::items::{Item, ItemId},
+    ///     Client, Requester,
+    /// };
     ///
     /// let client = Client::default();
-    /// let from_cache: Option<Item> = client.try_get(&19721);
+    /// let from_cache: Option<Item> = client.try_get(&ItemId(19721));
     /// ```
     async fn try_get<
         T: DeserializeOwned + Clone + Endpoint + Send + Sync + 'static,
@@ -165,6 +299,30 @@ pub trait Requester<const AUTHENTICATED: bool, const FORCE: bool>: Sized + Sync
         get_or_ids::<T, Vec<I>, Self, AUTHENTICATED, FORCE>(self).await
     }
 
+    /// request all available ids below `parent` of a nested endpoint
+    ///
+    /// `ids` only works for endpoints at the top of the hierarchy
+    async fn nested_ids<
+        T: DeserializeOwned + EndpointWithId<IdType = Nested<P, I>> + Clone + Send + Sync + 'static,
+        P: PathId + Hash + Clone + Send + Sync + 'static,
+        I: DeserializeOwned + Hash + Clone + Send + Sync + 'static,
+    >(
+        &self,
+        parent: impl Into<P> + Send,
+    ) -> EndpointResult<Vec<Nested<P, I>>> {
+        let parent = parent.into();
+        let mut segments = Vec::new();
+        parent.segments(&mut segments);
+        let path = fill_path(T::URL, &segments);
+        let ids =
+            get_cached::<T, Vec<I>, P, Self, AUTHENTICATED, FORCE>(self, &parent, &path, None)
+                .await?;
+        Ok(ids
+            .into_iter()
+            .map(|id| Nested::new(parent.clone(), id))
+            .collect())
+    }
+
     /// request multiple ids at once
     async fn many<
         T: DeserializeOwned
@@ -179,100 +337,33 @@ pub trait Requester<const AUTHENTICATED: bool, const FORCE: bool>: Sized + Sync
         &self,
         ids: Vec<impl Into<I> + Send>,
     ) -> EndpointResult<Vec<T>> {
-        let mut result = Vec::with_capacity(ids.len());
-        let ids = if !FORCE {
-            let ids = extract_many_from_cache(self, ids, &mut result).await;
-            if ids.is_empty() {
-                return Ok(result);
-            }
-            ids
-        } else {
-            ids.into_iter().map(|id| id.into()).collect()
-        };
-
-        let mut txs = FxHashMap::with_capacity_and_hasher(ids.len(), Default::default());
-        let mut rxs = Vec::with_capacity(ids.len());
-        let mut remaining_ids = Vec::with_capacity(ids.len());
-        for id in ids {
-            let retain = loop {
-                let either =
-                    check_inflight::<T, I, T>(&self.client().inflight, &id, self.client().language)
-                        .await;
-                match either {
-                    Some(Either::Left(rx)) => {
-                        rxs.push(rx);
-                        break false;
-                    }
-                    Some(Either::Right(tx)) => {
-                        txs.insert(id.clone(), tx);
-                        break true;
-                    }
-                    None => {
-                        if let Some(c) =
-                            check_cache::<T, I, T, Self, AUTHENTICATED, FORCE>(self, &id).await
-                        {
-                            result.push(c);
-                            break false;
-                        }
-                    }
-                }
-            };
-            if retain {
-                remaining_ids.push(id);
-            }
-        }
-
-        let result = Mutex::new(result);
-        let txs = Mutex::new(txs);
-        let chunks = join_ids(&remaining_ids);
-        let futs: FuturesUnordered<_> = chunks
-            .into_iter()
-            .map(|rest| {
-                let rest = Some(format!("ids={rest}"));
-                async {
-                    let request =
-                        build_request::<T, _, Self, AUTHENTICATED, FORCE>(self, T::URL, rest)?;
-
-                    let response = exec_req::<Self, AUTHENTICATED, FORCE>(self, request).await?;
-                    let mut result = result.lock().await;
-                    let index = result.len();
-                    // TODO: consider postponing the locking
-                    cache_response_many(self, response, &mut result).await?;
-
-                    let mut txs = txs.lock().await;
-                    for x in result.iter().skip(index) {
-                        let tx = txs
-                            .remove(x.id())
-                            .expect("received unexpected entry from api");
-                        // ignoring the error is fine here
-                        // the receiving side will check the cache if nothing got sent
-                        let _ = tx.lock().await.send(x.clone());
-                    }
-                    Result::<(), EndpointError>::Ok(())
-                }
-            })
-            .collect();
-        let mut futs = futs.into_future();
-        let mut error = None;
-        while let (Some(res), fut) = futs.await {
-            futs = fut.into_future();
-            if let Err(e) = res {
-                error = Some(e);
-            }
-        }
-        if let Some(e) = error {
-            return Err(e);
-        }
-
-        let mut result = result.into_inner();
-        for mut rx in rxs {
-            // TODO: check cache again
-            result.push(rx.recv().await?);
-        }
-
+        let (result, _) = get_many::<T, I, _, Self, AUTHENTICATED, FORCE>(self, ids, false).await?;
         Ok(result)
     }
 
+    /// request multiple ids at once, skipping entities that fail to
+    /// deserialize
+    ///
+    /// every entity is deserialized on its own, so an unknown variant only
+    /// affects that entity instead of the whole batch. The successfully
+    /// parsed entities are returned and cached as usual, the failures are
+    /// returned alongside them.
+    async fn many_lenient<
+        T: DeserializeOwned
+            + EndpointWithId<IdType = I>
+            + BulkEndpoint
+            + Clone
+            + Send
+            + Sync
+            + 'static,
+        I: Display + DeserializeOwned + Hash + Clone + Eq + Send + Sync + 'static,
+    >(
+        &self,
+        ids: Vec<impl Into<I> + Send>,
+    ) -> EndpointResult<(Vec<T>, Vec<JsonError>)> {
+        get_many::<T, I, _, Self, AUTHENTICATED, FORCE>(self, ids, true).await
+    }
+
     /// requests a page of items and returns the number of total items across
     /// all pages
     async fn page<T: DeserializeOwned + PagedEndpoint + Clone + Send + Sync + 'static>(
@@ -285,9 +376,10 @@ pub trait Requester<const AUTHENTICATED: bool, const FORCE: bool>: Sized + Sync
         let request =
             build_request::<T, _, Self, AUTHENTICATED, FORCE>(self, T::URL, Some(queries))?;
 
-        let response = exec_req::<Self, AUTHENTICATED, FORCE>(self, request).await?;
+        let response = exec_req::<T, Self, AUTHENTICATED, FORCE>(self, request).await?;
         let count = get_header(&response, "x-result-total").unwrap_or(0);
-        let (_expires, res): (_, Vec<T>) = parse_response(self, response).await?;
+        let (_expires, res): (_, Vec<T>) =
+            parse_response::<T, _, _, AUTHENTICATED, FORCE>(self, response).await?;
         result.extend_from_slice(&res);
 
         Ok(count)
@@ -322,6 +414,30 @@ pub trait Requester<const AUTHENTICATED: bool, const FORCE: bool>: Sized + Sync
         }
     }
 
+    /// requests all items like [`Self::all`], skipping entities that fail to
+    /// deserialize
+    ///
+    /// see [`Self::many_lenient`]
+    async fn all_lenient<
+        T: DeserializeOwned
+            + EndpointWithId<IdType = I>
+            + BulkEndpoint
+            + Clone
+            + Send
+            + Sync
+            + 'static,
+        I: Display + DeserializeOwned + Hash + Clone + Send + Sync + Eq + 'static,
+    >(
+        &self,
+    ) -> EndpointResult<(Vec<T>, Vec<JsonError>)> {
+        if T::ALL {
+            get_ids_all::<T, I, Self, AUTHENTICATED, FORCE>(self, true).await
+        } else {
+            let ids = self.ids::<T, I>().await?;
+            self.many_lenient(ids).await
+        }
+    }
+
     /// Gets all items by querying ids=all
     ///
     /// use [`Self::all`] to use the most efficient way to request all items
@@ -337,18 +453,7 @@ pub trait Requester<const AUTHENTICATED: bool, const FORCE: bool>: Sized + Sync
     >(
         &self,
     ) -> EndpointResult<Vec<T>> {
-        if !T::ALL {
-            return Err(EndpointError::UnsupportedEndpointQuery);
-        }
-
-        let request =
-            build_request::<T, _, Self, AUTHENTICATED, FORCE>(self, T::URL, Some("ids=all"))?;
-
-        let response = exec_req::<Self, AUTHENTICATED, FORCE>(self, request).await?;
-        let count = get_header(&response, "x-result-total").unwrap_or(0);
-        let mut result = Vec::with_capacity(count);
-        cache_response_many(self, response, &mut result).await?;
-
+        let (result, _) = get_ids_all::<T, I, Self, AUTHENTICATED, FORCE>(self, false).await?;
         Ok(result)
     }
 
@@ -391,6 +496,30 @@ pub trait Requester<const AUTHENTICATED: bool, const FORCE: bool>: Sized + Sync
         let ids = self.ids::<T, I>().await?;
         self.many(ids).await
     }
+
+    /// requests every entity `value` refers to by id
+    ///
+    /// the ids are batched per endpoint and requested with [`Self::many`], so
+    /// cached entities are not requested again
+    async fn resolve<T: References + Sync + ?Sized>(&self, value: &T) -> EndpointResult<Resolved> {
+        let mut set = ReferenceSet::default();
+        value.references(&mut set);
+
+        let (items, recipes, colors, currencies, worlds) = futures::try_join!(
+            resolve_many::<Item, _, Self, AUTHENTICATED, FORCE>(self, set.items),
+            resolve_many::<Recipe, _, Self, AUTHENTICATED, FORCE>(self, set.recipes),
+            resolve_many::<Color, _, Self, AUTHENTICATED, FORCE>(self, set.colors),
+            resolve_many::<Currency, _, Self, AUTHENTICATED, FORCE>(self, set.currencies),
+            resolve_many::<World, _, Self, AUTHENTICATED, FORCE>(self, set.worlds),
+        )?;
+        Ok(Resolved {
+            items,
+            recipes,
+            colors,
+            currencies,
+            worlds,
+        })
+    }
 }
 
 struct SenderGuard<'client, T: Send> {
@@ -427,8 +556,13 @@ async fn check_inflight<
     inflight: &'client Inflight,
     id: &I,
     lang: Language,
+    version: Option<&str>,
 ) -> Option<Either<Receiver<H>, SenderGuard<'client, H>>> {
-    let hash = hash::<H, I>(id, T::LOCALE.then_some(lang));
+    let lang = T::LOCALE.then_some(lang);
+    let hash = match version {
+        Some(version) => hash::<H, _>(&VersionedKey::new(id, version), lang),
+        None => hash::<H, I>(id, lang),
+    };
     let mut locked = inflight.lock().await;
     Some(match locked.entry(hash) {
         Entry::Occupied(mut e) => {
@@ -438,6 +572,7 @@ async fn check_inflight<
                 .unwrap();
             let r = r.upgrade()?;
             let r = r.lock().await;
+            debug!(endpoint = T::URL, "joining inflight request");
             Either::Left(r.subscribe())
         }
         Entry::Vacant(e) => {
@@ -463,15 +598,214 @@ async fn check_cache<
 >(
     req: &Req,
     id: &I,
+) -> Option<T> {
+    check_endpoint_cache::<T, I, E, Req, A, F>(req, id, E::URL).await
+}
+
+/// like [`check_cache`], for endpoints that are only known at runtime
+async fn check_endpoint_cache<
+    T: Clone + Send + Sync + 'static,
+    I: Hash + Sync + 'static,
+    E: Endpoint,
+    Req: Requester<A, F>,
+    const A: bool,
+    const F: bool,
+>(
+    req: &Req,
+    id: &I,
+    endpoint: &'static str,
 ) -> Option<T> {
     if !F {
         let mut cache = req.client().cache.lock().await;
-        cache.get::<T, I, E>(id, req.client().language).await
+        let cached = cache_get::<T, I, E, Req, A, F>(req, &mut cache, id).await;
+        debug!(endpoint, hit = cached.is_some(), "cache lookup");
+        if let Some(metrics) = &req.client().metrics {
+            metrics.cache(endpoint, cached.is_some());
+        }
+        cached
     } else {
         None
     }
 }
 
+async fn get_many<
+    T: DeserializeOwned + EndpointWithId<IdType = I> + BulkEndpoint + Clone + Send + Sync + 'static,
+    I: Display + DeserializeOwned + Hash + Clone + Eq + Send + Sync + 'static,
+    Id: Into<I> + Send,
+    Req: Requester<A, F>,
+    const A: bool,
+    const F: bool,
+>(
+    req: &Req,
+    ids: Vec<Id>,
+    lenient: bool,
+) -> EndpointResult<(Vec<T>, Vec<JsonError>)> {
+    let mut result = Vec::with_capacity(ids.len());
+    let ids = if !F {
+        let ids = extract_many_from_cache(req, ids, &mut result).await;
+        if ids.is_empty() {
+            return Ok((result, Vec::new()));
+        }
+        ids
+    } else {
+        ids.into_iter().map(|id| id.into()).collect()
+    };
+
+    let mut txs = FxHashMap::with_capacity_and_hasher(ids.len(), Default::default());
+    let mut rxs = Vec::with_capacity(ids.len());
+    let mut remaining_ids = Vec::with_capacity(ids.len());
+    for id in ids {
+        let retain = loop {
+            let (inflight, lang) = (&req.client().inflight, req.client().language);
+            let either = check_inflight::<T, I, T>(inflight, &id, lang, req.schema_version()).await;
+            match either {
+                Some(Either::Left(rx)) => {
+                    rxs.push((id.clone(), rx));
+                    break false;
+                }
+                Some(Either::Right(tx)) => {
+                    txs.insert(id.clone(), tx);
+                    break true;
+                }
+                None => {
+                    if let Some(c) = check_cache::<T, I, T, Req, A, F>(req, &id).await {
+                        result.push(c);
+                        break false;
+                    }
+                }
+            }
+        };
+        if retain {
+            remaining_ids.push(id);
+        }
+    }
+
+    let result = Mutex::new(result);
+    let failures = Mutex::new(Vec::new());
+    let txs = Mutex::new(txs);
+    // nested ids can only be requested together with their siblings
+    let mut groups: BTreeMap<Vec<String>, Vec<I>> = BTreeMap::new();
+    for id in remaining_ids {
+        groups.entry(T::parents(&id)).or_default().push(id);
+    }
+    let futs: FuturesUnordered<_> = groups
+        .into_iter()
+        .flat_map(|(parents, ids)| {
+            let path = fill_path(T::URL, &parents);
+            let sibling = ids[0].clone();
+            join_ids(&ids)
+                .into_iter()
+                .map(move |rest| (path.clone(), sibling.clone(), rest))
+        })
+        .map(|(path, sibling, rest)| {
+            let rest = Some(format!("ids={rest}"));
+            let (result, failures, txs) = (&result, &failures, &txs);
+            async move {
+                let request = build_request::<T, _, Req, A, F>(req, &path, rest)?;
+
+                let response = exec_req::<T, Req, A, F>(req, request).await?;
+                let mut result = result.lock().await;
+                let index = result.len();
+                // TODO: consider postponing the locking
+                let failed =
+                    cache_response_many(req, response, &mut result, lenient, Some(&sibling))
+                        .await?;
+                failures.lock().await.extend(failed);
+
+                let mut txs = txs.lock().await;
+                for x in result.iter().skip(index) {
+                    let tx = txs
+                        .remove(x.id())
+                        .expect("received unexpected entry from api");
+                    // ignoring the error is fine here
+                    // the receiving side will check the cache if nothing got sent
+                    let _ = tx.lock().await.send(x.clone());
+                }
+                Result::<(), EndpointError>::Ok(())
+            }
+        })
+        .collect();
+    let mut futs = futs.into_future();
+    let mut error = None;
+    while let (Some(res), fut) = futs.await {
+        futs = fut.into_future();
+        if let Err(e) = res {
+            error = Some(e);
+        }
+    }
+    if let Some(e) = error {
+        return Err(e);
+    }
+    // the senders of entities that failed to deserialize are still around,
+    // waiting on them while another request waits on ours would never finish
+    drop(txs);
+
+    let mut result = result.into_inner();
+    let mut failures = failures.into_inner();
+    let mut retry = Vec::new();
+    for (id, mut rx) in rxs {
+        match rx.recv().await {
+            Ok(x) => result.push(x),
+            // the request we joined failed, requesting the ids again surfaces
+            // the actual error
+            Err(RecvError::Closed) => retry.push(id),
+            Err(e) => return Err(e.into()),
+        }
+    }
+    if !retry.is_empty() {
+        if let Some(metrics) = &req.client().metrics {
+            metrics.retry(T::URL);
+        }
+        let (retried, failed) =
+            Box::pin(get_many::<T, I, I, Req, A, F>(req, retry, lenient)).await?;
+        result.extend(retried);
+        failures.extend(failed);
+    }
+
+    Ok((result, failures))
+}
+
+async fn resolve_many<
+    T: DeserializeOwned + EndpointWithId<IdType = I> + BulkEndpoint + Clone + Send + Sync + 'static,
+    I: Display + DeserializeOwned + Hash + Clone + Eq + Send + Sync + 'static,
+    Req: Requester<A, F>,
+    const A: bool,
+    const F: bool,
+>(
+    req: &Req,
+    ids: BTreeSet<I>,
+) -> EndpointResult<HashMap<I, T>> {
+    if ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let (result, _) = get_many::<T, I, _, Req, A, F>(req, ids.into_iter().collect(), false).await?;
+    Ok(result.into_iter().map(|x| (x.id().clone(), x)).collect())
+}
+
+async fn get_ids_all<
+    T: DeserializeOwned + EndpointWithId<IdType = I> + BulkEndpoint + Clone + Send + Sync + 'static,
+    I: Display + DeserializeOwned + Hash + Clone + Sync + 'static,
+    Req: Requester<A, F>,
+    const A: bool,
+    const F: bool,
+>(
+    req: &Req,
+    lenient: bool,
+) -> EndpointResult<(Vec<T>, Vec<JsonError>)> {
+    if !T::ALL {
+        return Err(EndpointError::UnsupportedEndpointQuery);
+    }
+
+    let request = build_request::<T, _, Req, A, F>(req, T::URL, Some("ids=all"))?;
+
+    let response = exec_req::<T, Req, A, F>(req, request).await?;
+    let count = get_header(&response, "x-result-total").unwrap_or(0);
+    let mut result = Vec::with_capacity(count);
+    let failures = cache_response_many(req, response, &mut result, lenient, None).await?;
+
+    Ok((result, failures))
+}
+
 async fn get_or_ids<
     T: DeserializeOwned + Endpoint + Clone + Send + Sync + 'static,
     K: DeserializeOwned + Clone + Send + Sync + 'static,
@@ -481,28 +815,119 @@ async fn get_or_ids<
 >(
     req: &Req,
 ) -> EndpointResult<K> {
+    get_cached::<T, K, (), Req, A, F>(req, &(), T::URL, None).await
+}
+
+/// requests `path` with the given query, caching the response under `key`
+async fn get_cached<
+    T: DeserializeOwned + Endpoint + Clone + Send + Sync + 'static,
+    K: DeserializeOwned + Clone + Send + Sync + 'static,
+    I: Hash + Sync + 'static,
+    Req: Requester<A, F>,
+    const A: bool,
+    const F: bool,
+>(
+    req: &Req,
+    key: &I,
+    path: &str,
+    queries: Option<String>,
+) -> EndpointResult<K> {
+    let lang = req.client().language;
+    if let Some(c) = check_cache::<K, I, T, Req, A, F>(req, key).await {
+        return Ok(c);
+    }
+
+    let tx = loop {
+        let inflight = &req.client().inflight;
+        let either = check_inflight::<K, I, T>(inflight, key, lang, req.schema_version()).await;
+        match either {
+            Some(Either::Left(mut rx)) => return rx.recv().await.map_err(Into::into),
+            Some(Either::Right(tx)) => break tx,
+            None => {
+                if let Some(c) = check_cache::<K, I, T, Req, A, F>(req, key).await {
+                    return Ok(c);
+                }
+            }
+        }
+    };
+
+    let request = build_request::<T, String, Req, A, F>(req, path, queries)?;
+
+    let response = exec_req::<T, Req, A, F>(req, request).await?;
+    let result = cache_response::<I, K, T, Req, A, F>(req, key, response).await?;
+    // ignoring the error is fine here
+    // the receiving side will check the cache if nothing got sent
+    let _ = tx.lock().await.send(result.clone());
+
+    Ok(result)
+}
+
+/// stands in for the endpoint of [`Requester::raw`] where a type is needed,
+/// only `AUTHENTICATED` and `LOCALE` are used
+struct RawEndpoint<const AUTHENTICATED: bool, const LOCALE: bool>;
+
+impl<const AUTHENTICATED: bool, const LOCALE: bool> Endpoint
+    for RawEndpoint<AUTHENTICATED, LOCALE>
+{
+    const AUTHENTICATED: bool = AUTHENTICATED;
+    const LOCALE: bool = LOCALE;
+    const URL: &'static str = "";
+    const VERSION: &'static str = "";
+}
+
+async fn get_raw<
+    E: Endpoint + Send + 'static,
+    Req: Requester<A, F>,
+    const A: bool,
+    const F: bool,
+>(
+    req: &Req,
+    info: &EndpointInfo,
+    path: &str,
+) -> EndpointResult<serde_json::Value> {
+    let key = path.to_string();
     let lang = req.client().language;
-    if let Some(c) = check_cache::<K, (), T, Req, A, F>(req, &()).await {
+    if let Some(c) =
+        check_endpoint_cache::<serde_json::Value, String, E, Req, A, F>(req, &key, info.url).await
+    {
         return Ok(c);
     }
 
     let tx = loop {
-        let either = check_inflight::<K, (), T>(&req.client().inflight, &(), lang).await;
+        let inflight = &req.client().inflight;
+        let either = check_inflight::<serde_json::Value, String, E>(
+            inflight,
+            &key,
+            lang,
+            req.schema_version(),
+        )
+        .await;
         match either {
             Some(Either::Left(mut rx)) => return rx.recv().await.map_err(Into::into),
             Some(Either::Right(tx)) => break tx,
             None => {
-                if let Some(c) = check_cache::<K, (), T, Req, A, F>(req, &()).await {
+                if let Some(c) = check_endpoint_cache::<serde_json::Value, String, E, Req, A, F>(
+                    req, &key, info.url,
+                )
+                .await
+                {
                     return Ok(c);
                 }
             }
         }
     };
 
-    let request = build_request::<T, String, Req, A, F>(req, T::URL, None)?;
+    let (path, queries) = match path.split_once('?') {
+        Some((path, queries)) => (path, Some(queries)),
+        None => (path, None),
+    };
+    let version = req.schema_version().unwrap_or(info.version);
+    let request =
+        build_versioned_request::<E, _, Req, A, F>(req, version, path.trim_matches('/'), queries)?;
 
-    let response = exec_req::<Req, A, F>(req, request).await?;
-    let result = cache_response::<(), K, T, Req, A, F>(req, &(), response).await?;
+    let response = exec_endpoint_req(req, request, info.url, version, info.permissions).await?;
+    let result =
+        cache_response::<String, serde_json::Value, E, Req, A, F>(req, &key, response).await?;
     // ignoring the error is fine here
     // the receiving side will check the cache if nothing got sent
     let _ = tx.lock().await.send(result.clone());
@@ -510,18 +935,138 @@ async fn get_or_ids<
     Ok(result)
 }
 
-async fn exec_req<Req: Requester<A, F>, const A: bool, const F: bool>(
+async fn exec_req<T: Endpoint, Req: Requester<A, F>, const A: bool, const F: bool>(
     req: &Req,
     request: Request<hyper::Body>,
 ) -> EndpointResult<Response<hyper::Body>> {
-    let time = { req.client().rate_limiter.lock().await.take(1).await? };
-    tokio::time::sleep(std::time::Duration::from_secs(time)).await;
+    let version = req.schema_version().unwrap_or(T::VERSION);
+    exec_endpoint_req(req, request, T::URL, version, T::PERMISSIONS).await
+}
 
-    req.client()
-        .client
-        .request(request)
-        .await
-        .map_err(Into::into)
+/// [`exec_req`] for endpoints that are only known at runtime
+async fn exec_endpoint_req<Req: Requester<A, F>, const A: bool, const F: bool>(
+    req: &Req,
+    request: Request<hyper::Body>,
+    endpoint: &'static str,
+    version: &'static str,
+    permissions: &[Permissions],
+) -> EndpointResult<Response<hyper::Body>> {
+    check_permissions(req, permissions).await?;
+
+    // the api key is sent as a header, so the url is safe to log
+    #[cfg(feature = "tracing")]
+    let span = tracing::debug_span!(
+        "request",
+        endpoint,
+        version,
+        url = %request.uri()
+    );
+    #[cfg(not(feature = "tracing"))]
+    let _ = version;
+
+    let response = send_request(req, request, endpoint);
+
+    #[cfg(feature = "tracing")]
+    let response = tracing::Instrument::instrument(response, span);
+
+    response.await
+}
+
+/// fails if the api key lacks a permission the endpoint needs
+///
+/// the permissions are requested from the tokeninfo endpoint once per key
+async fn check_permissions<Req: Requester<A, F>, const A: bool, const F: bool>(
+    req: &Req,
+    required: &[Permissions],
+) -> EndpointResult<()> {
+    if !A || required.is_empty() {
+        return Ok(());
+    }
+
+    let permissions = req
+        .client()
+        .permissions
+        .get_or_try_init(|| async {
+            // the override is meant for the endpoint, not for this check
+            let request = build_versioned_request::<Tokeninfo, String, Req, A, F>(
+                req,
+                Tokeninfo::VERSION,
+                Tokeninfo::URL,
+                None,
+            )?;
+            let response = send_request(req, request, Tokeninfo::URL).await?;
+            let (_, tokeninfo): (_, Tokeninfo) = parse_versioned_response::<Tokeninfo, _, _, A, F>(
+                req,
+                Tokeninfo::VERSION,
+                response,
+            )
+            .await?;
+            debug!(permissions = ?tokeninfo.permissions, "tokeninfo");
+            Ok::<_, EndpointError>(tokeninfo.permissions)
+        })
+        .await?;
+
+    match required.iter().find(|p| !permissions.contains(p)) {
+        Some(missing) => Err(EndpointError::MissingPermission(missing.clone())),
+        None => Ok(()),
+    }
+}
+
+async fn send_request<Req: Requester<A, F>, const A: bool, const F: bool>(
+    req: &Req,
+    request: Request<hyper::Body>,
+    endpoint: &'static str,
+) -> EndpointResult<Response<hyper::Body>> {
+    let _permit = match &req.client().concurrency {
+        Some(semaphore) => Some(
+            semaphore
+                .acquire()
+                .await
+                .expect("concurrency semaphore is never closed"),
+        ),
+        None => None,
+    };
+
+    {
+        // holding the slot while sleeping lets requests with a higher priority
+        // that arrive in the meantime go next
+        let _slot = req.client().scheduler.acquire(req.request_priority()).await;
+        let time = { req.client().rate_limiter.lock().await.take(1).await? };
+        debug!(wait = ?time, "rate limiter");
+        if let (Some(metrics), false) = (&req.client().metrics, time.is_zero()) {
+            metrics.rate_limit_wait(endpoint, time);
+        }
+        tokio::time::sleep(time).await;
+    }
+
+    let start = std::time::Instant::now();
+    let response = req.client().client.request(request);
+    let response = match req.client().timeout {
+        Some(timeout) => tokio::time::timeout(timeout, response)
+            .await
+            .map_err(|_| EndpointError::Timeout)
+            .and_then(|x| x.map_err(Into::into)),
+        None => response.await.map_err(Into::into),
+    };
+
+    if let Some(metrics) = &req.client().metrics {
+        let status = match &response {
+            Ok(response) => response.status().into(),
+            Err(_) => StatusClass::Failed,
+        };
+        metrics.request(endpoint, status, start.elapsed());
+    }
+
+    #[cfg(feature = "tracing")]
+    match &response {
+        Ok(response) => tracing::debug!(
+            status = response.status().as_u16(),
+            latency = ?start.elapsed(),
+            "response"
+        ),
+        Err(e) => tracing::warn!(error = %e, latency = ?start.elapsed(), "request failed"),
+    }
+    response
 }
 
 fn build_request<
@@ -534,21 +1079,45 @@ fn build_request<
     req: &Req,
     path: &str,
     extra_queries: Option<Q>,
+) -> Result<Request<hyper::Body>, EndpointError> {
+    let version = req.schema_version().unwrap_or(T::VERSION);
+    build_versioned_request::<T, Q, Req, A, F>(req, version, path, extra_queries)
+}
+
+/// [`build_request`] for a specific schema version
+fn build_versioned_request<
+    T: Endpoint,
+    Q: Into<String>,
+    Req: Requester<A, F>,
+    const A: bool,
+    const F: bool,
+>(
+    req: &Req,
+    version: &str,
+    path: &str,
+    extra_queries: Option<Q>,
 ) -> Result<Request<hyper::Body>, EndpointError> {
     if T::AUTHENTICATED && !A {
         return Err(EndpointError::NotAuthenticated);
     }
+    // a nested endpoint requested without its parents
+    if path.contains("/:") {
+        return Err(EndpointError::UnsupportedEndpointQuery);
+    }
 
     let uri = build_query::<T, Q>(
         &req.client().host,
         path,
         req.client().language,
         extra_queries,
-    );
+    )?;
 
     let mut request = hyper::Request::builder().uri(uri);
 
-    request = request.header("X-Schema-Version", T::VERSION);
+    for (name, value) in req.client().headers.iter() {
+        request = request.header(name, value);
+    }
+    request = request.header("X-Schema-Version", version);
     if T::AUTHENTICATED {
         request = request.header(
             "Authorization",
@@ -556,7 +1125,10 @@ fn build_request<
         );
     }
 
-    let request = request.body(hyper::Body::empty()).unwrap();
+    let mut request = request.body(hyper::Body::empty())?;
+    if let Some(hook) = &req.client().request_hook {
+        hook(&mut request);
+    }
 
     Ok(request)
 }
@@ -566,8 +1138,10 @@ fn build_query<T: Endpoint, Q: Into<String>>(
     path: &str,
     lang: Language,
     extra_queries: Option<Q>,
-) -> Uri {
-    let (scheme, host) = host.split_once("://").expect("invalid host");
+) -> Result<Uri, EndpointError> {
+    let (scheme, authority) = host
+        .split_once("://")
+        .ok_or_else(|| EndpointError::InvalidHost(host.to_string()))?;
 
     let mut args = Vec::new();
     if T::LOCALE {
@@ -583,10 +1157,72 @@ fn build_query<T: Endpoint, Q: Into<String>>(
 
     Uri::builder()
         .scheme(scheme)
-        .authority(host)
+        .authority(authority)
         .path_and_query(pnq)
         .build()
-        .expect("invalid uri")
+        .map_err(Into::into)
+}
+
+/// keeps the entries of requests with a schema version override apart from
+/// the others
+#[derive(Hash)]
+struct VersionedKey(u64);
+
+impl VersionedKey {
+    fn new<I: Hash>(id: &I, version: &str) -> Self {
+        let mut hasher = FxHasher::default();
+        id.hash(&mut hasher);
+        version.hash(&mut hasher);
+        Self(hasher.finish())
+    }
+}
+
+async fn cache_get<
+    T: Clone + Send + Sync + 'static,
+    I: Hash + Sync + 'static,
+    E: Endpoint,
+    Req: Requester<A, F>,
+    const A: bool,
+    const F: bool,
+>(
+    req: &Req,
+    cache: &mut Req::Caching,
+    id: &I,
+) -> Option<T> {
+    let lang = req.client().language;
+    match req.schema_version() {
+        Some(version) => {
+            cache
+                .get::<T, _, E>(&VersionedKey::new(id, version), lang)
+                .await
+        }
+        None => cache.get::<T, I, E>(id, lang).await,
+    }
+}
+
+async fn cache_insert<
+    T: Clone + Send + Sync + 'static,
+    I: Hash + Sync + 'static,
+    E: Endpoint,
+    Req: Requester<A, F>,
+    const A: bool,
+    const F: bool,
+>(
+    req: &Req,
+    cache: &mut Req::Caching,
+    id: &I,
+    value: T,
+    expires: NaiveDateTime,
+) {
+    let lang = req.client().language;
+    match req.schema_version() {
+        Some(version) => {
+            cache
+                .insert::<T, _, E>(&VersionedKey::new(id, version), value, expires, lang)
+                .await
+        }
+        None => cache.insert::<T, I, E>(id, value, expires, lang).await,
+    }
 }
 
 /// returns the remaining ids not found in cache
@@ -605,12 +1241,21 @@ async fn extract_many_from_cache<
     let mut cache = req.client().cache.lock().await;
     for i in ids {
         let i = i.into();
-        if let Some(cached) = cache.get::<K, I, K>(&i, req.client().language).await {
-            result.push(cached);
-        } else {
-            rest.push(i);
+        let cached = cache_get::<K, I, K, Req, A, F>(req, &mut cache, &i).await;
+        if let Some(metrics) = &req.client().metrics {
+            metrics.cache(K::URL, cached.is_some());
+        }
+        match cached {
+            Some(cached) => result.push(cached),
+            None => rest.push(i),
         }
     }
+    debug!(
+        endpoint = K::URL,
+        hits = result.len(),
+        misses = rest.len(),
+        "cache lookup"
+    );
     rest
 }
 
@@ -626,17 +1271,19 @@ async fn cache_response<
     id: &I,
     response: Response<hyper::Body>,
 ) -> Result<K, EndpointError> {
-    let (expires, result): (_, K) = parse_response(req, response).await?;
+    let (expires, result): (_, K) = parse_response::<T, _, _, A, F>(req, response).await?;
     let res = result.clone();
     {
         let mut cache = req.client().cache.lock().await;
-        cache
-            .insert::<K, I, T>(id, res, expires, req.client().language)
-            .await;
+        cache_insert::<K, I, T, Req, A, F>(req, &mut cache, id, res, expires).await;
     }
     Ok(result)
 }
 
+/// returns the entities that failed to deserialize if `lenient` is set
+///
+/// `sibling` is one of the requested ids, its parents are copied onto the
+/// entities of nested endpoints
 async fn cache_response_many<
     I: Display + Hash + Sync + 'static,
     K: DeserializeOwned + BulkEndpoint + EndpointWithId<IdType = I> + Clone + Send + Sync + 'static,
@@ -647,34 +1294,136 @@ async fn cache_response_many<
     req: &Req,
     response: Response<hyper::Body>,
     result: &mut Vec<K>,
-) -> Result<(), EndpointError> {
-    let (expires, res): (_, Vec<K>) = parse_response(req, response).await?;
+    lenient: bool,
+    sibling: Option<&I>,
+) -> Result<Vec<JsonError>, EndpointError> {
+    let (expires, res, failures) = if lenient {
+        parse_response_lenient::<K, Req, A, F>(req, response).await?
+    } else {
+        let (expires, res) = parse_response::<K, Vec<K>, Req, A, F>(req, response).await?;
+        (expires, res, Vec::new())
+    };
     {
         let mut cache = req.client().cache.lock().await;
-        for t in res {
-            cache
-                .insert::<K, I, K>(t.id(), t.clone(), expires, req.client().language)
-                .await;
+        for mut t in res {
+            if let Some(sibling) = sibling {
+                t.set_parents(sibling);
+            }
+            cache_insert::<K, I, K, Req, A, F>(req, &mut cache, t.id(), t.clone(), expires).await;
             result.push(t);
         }
     }
-    Ok(())
+    Ok(failures)
 }
 
 async fn parse_response<
+    E: Endpoint,
+    K: DeserializeOwned + Clone + Send + Sync + 'static,
+    Req: Requester<A, F>,
+    const A: bool,
+    const F: bool,
+>(
+    req: &Req,
+    response: Response<hyper::Body>,
+) -> Result<(NaiveDateTime, K), EndpointError> {
+    let version = req.schema_version().unwrap_or(E::VERSION);
+    parse_versioned_response::<E, K, Req, A, F>(req, version, response).await
+}
+
+/// [`parse_response`] for a specific schema version
+async fn parse_versioned_response<
+    E: Endpoint,
     K: DeserializeOwned + Clone + Send + Sync + 'static,
     Req: Requester<A, F>,
     const A: bool,
     const F: bool,
 >(
     req: &Req,
+    version: &'static str,
     response: Response<hyper::Body>,
 ) -> Result<(NaiveDateTime, K), EndpointError> {
+    let (expires, body) = read_response(req, E::URL, response).await?;
+    let de = &mut serde_json::Deserializer::from_slice(&body);
+    let result: K = match serde_path_to_error::deserialize(de) {
+        Ok(result) => result,
+        Err(e) => {
+            let e = json_error::<E>(e, version, &body, req.client().error_body);
+            warn!(error = %e, "failed to deserialize response");
+            return Err(EndpointError::InvalidJsonResponse(Box::new(e)));
+        }
+    };
+    Ok((expires, result))
+}
+
+/// deserializes every entity of a bulk response on its own
+async fn parse_response_lenient<
+    K: DeserializeOwned + BulkEndpoint + Clone + Send + Sync + 'static,
+    Req: Requester<A, F>,
+    const A: bool,
+    const F: bool,
+>(
+    req: &Req,
+    response: Response<hyper::Body>,
+) -> Result<(NaiveDateTime, Vec<K>, Vec<JsonError>), EndpointError> {
+    let version = req.schema_version().unwrap_or(K::VERSION);
+    let (expires, body) = read_response(req, K::URL, response).await?;
+    let de = &mut serde_json::Deserializer::from_slice(&body);
+    let entities: Vec<&RawValue> = match serde_path_to_error::deserialize(de) {
+        Ok(entities) => entities,
+        Err(e) => {
+            let e = json_error::<K>(e, version, &body, req.client().error_body);
+            warn!(error = %e, "failed to deserialize response");
+            return Err(EndpointError::InvalidJsonResponse(Box::new(e)));
+        }
+    };
+
+    let mut result = Vec::with_capacity(entities.len());
+    let mut failures = Vec::new();
+    for (index, raw) in entities.into_iter().enumerate() {
+        let de = &mut serde_json::Deserializer::from_str(raw.get());
+        match serde_path_to_error::deserialize(de) {
+            Ok(entity) => result.push(entity),
+            Err(e) => {
+                let path = match e.path().to_string() {
+                    path if path == "." => format!("[{}]", index),
+                    path if path.starts_with('[') => format!("[{}]{}", index, path),
+                    path => format!("[{}].{}", index, path),
+                };
+                let id = serde_json::from_str(raw.get())
+                    .ok()
+                    .as_ref()
+                    .and_then(entity_id);
+                let e = JsonError {
+                    endpoint: K::URL,
+                    version,
+                    path,
+                    id,
+                    source: e.into_inner(),
+                    body: req.client().error_body.then(|| raw.get().to_string()),
+                };
+                warn!(error = %e, "skipping entity that failed to deserialize");
+                failures.push(e);
+            }
+        }
+    }
+    Ok((expires, result, failures))
+}
+
+/// checks the status and returns the cache expiry and the body
+async fn read_response<Req: Requester<A, F>, const A: bool, const F: bool>(
+    req: &Req,
+    endpoint: &'static str,
+    response: Response<hyper::Body>,
+) -> Result<(NaiveDateTime, Bytes), EndpointError> {
     let status = response.status();
     if !status.is_success() {
+        warn!(status = status.as_u16(), "api returned an error");
         return Err(EndpointError::ApiError(match status.as_u16() {
             401 => ApiError::Unauthorized,
             429 => {
+                if let Some(metrics) = &req.client().metrics {
+                    metrics.rate_limited(endpoint);
+                }
                 let _ = req.client().rate_limiter.lock().await.penalize().await;
                 ApiError::RateLimited
             }
@@ -686,9 +1435,72 @@ async fn parse_response<
         }));
     }
     let expires = get_cache_expiry(req, &response);
-    let body = hyper::body::aggregate(response.into_body()).await?;
-    let result: K = serde_json::from_reader(&mut body.reader())?;
-    Ok((expires, result))
+    let body = hyper::body::to_bytes(response.into_body()).await?;
+    debug!(bytes = body.len(), "response body");
+    Ok((expires, body))
+}
+
+fn json_error<E: Endpoint>(
+    error: serde_path_to_error::Error<serde_json::Error>,
+    version: &'static str,
+    body: &[u8],
+    keep_body: bool,
+) -> JsonError {
+    let path = error.path().to_string();
+    // bulk responses are arrays, the entity is the first element of the path
+    let index = match error.path().iter().next() {
+        Some(serde_path_to_error::Segment::Seq { index }) => Some(*index),
+        _ => None,
+    };
+    let source = error.into_inner();
+
+    let value: Option<serde_json::Value> = serde_json::from_slice(body).ok();
+    let entity = value.as_ref().and_then(|value| match index {
+        Some(index) => value.get(index),
+        None => Some(value),
+    });
+    let id = entity.and_then(entity_id);
+    let body = keep_body.then(|| match entity {
+        Some(entity) => entity.to_string(),
+        None => snippet(body, &source),
+    });
+
+    JsonError {
+        endpoint: E::URL,
+        version,
+        path,
+        id,
+        source,
+        body,
+    }
+}
+
+fn entity_id(entity: &serde_json::Value) -> Option<String> {
+    entity.get("id").map(|id| match id {
+        serde_json::Value::String(id) => id.clone(),
+        id => id.to_string(),
+    })
+}
+
+/// returns the part of the body around the error location
+fn snippet(body: &[u8], error: &serde_json::Error) -> String {
+    const CONTEXT: usize = 256;
+    let body = String::from_utf8_lossy(body);
+    let line_start: usize = body
+        .split_inclusive('\n')
+        .take(error.line().saturating_sub(1))
+        .map(str::len)
+        .sum();
+    let offset = (line_start + error.column()).min(body.len());
+    let mut start = offset.saturating_sub(CONTEXT);
+    while !body.is_char_boundary(start) {
+        start -= 1;
+    }
+    let mut end = (offset + CONTEXT).min(body.len());
+    while !body.is_char_boundary(end) {
+        end += 1;
+    }
+    body[start..end].to_string()
 }
 
 fn get_cache_expiry<Req: Requester<A, F>, const A: bool, const F: bool>(
@@ -704,6 +1516,27 @@ fn get_cache_expiry<Req: Requester<A, F>, const A: bool, const F: bool>(
     Utc::now().naive_utc() + expires
 }
 
+/// replaces the `:placeholders` of a nested endpoint's url with `parents`, in
+/// order
+fn fill_path(url: &str, parents: &[String]) -> String {
+    if parents.is_empty() {
+        return url.to_string();
+    }
+    let mut parents = parents.iter();
+    url.split('/')
+        .map(|segment| {
+            if segment.starts_with(':') {
+                parents
+                    .next()
+                    .map_or(segment.into(), |p| urlencoding::encode(p))
+            } else {
+                segment.into()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
 /// concatenates ids, separated by comma: 1,2,3,4
 /// chunked in 200 per batch
 ///
@@ -0,0 +1,150 @@
+use std::{
+    cmp::Ordering,
+    collections::BinaryHeap,
+    sync::{Arc, Mutex},
+};
+
+use tokio::sync::oneshot;
+
+/// the priority of a request when waiting for the rate limiter
+///
+/// waiting requests with a higher priority are always handed the next slot
+/// first, requests of the same priority are handled in order
+///
+/// priorities only apply within one [`Client`](crate::Client) and the clients
+/// derived from it. Separately created clients that share a rate limiter wait
+/// for it without regard to each other's priorities.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Priority {
+    /// background work, e.g. syncing all items
+    Low,
+    #[default]
+    Normal,
+    /// interactive lookups
+    High,
+}
+
+/// hands out access to the rate limiter one request at a time, ordered by
+/// priority
+///
+/// every client has its own, sharing a rate limiter doesn't share the queue
+#[derive(Default)]
+pub(crate) struct Scheduler {
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    busy: bool,
+    next: u64,
+    queue: BinaryHeap<Waiter>,
+}
+
+struct Waiter {
+    priority: Priority,
+    /// lower numbers are older
+    seq: u64,
+    tx: oneshot::Sender<()>,
+}
+
+impl PartialEq for Waiter {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Waiter {}
+
+impl PartialOrd for Waiter {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Waiter {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
+/// releases the slot to the next waiting request when dropped
+pub(crate) struct Slot {
+    scheduler: Arc<Scheduler>,
+}
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        self.scheduler.release();
+    }
+}
+
+/// makes sure a slot that was granted to a cancelled request is passed on
+struct Pending {
+    rx: oneshot::Receiver<()>,
+    scheduler: Arc<Scheduler>,
+    granted: bool,
+}
+
+impl Drop for Pending {
+    fn drop(&mut self) {
+        if self.granted {
+            return;
+        }
+        self.rx.close();
+        if self.rx.try_recv().is_ok() {
+            self.scheduler.release();
+        }
+    }
+}
+
+impl Scheduler {
+    /// waits until no request with a higher priority is waiting and the slot is
+    /// free
+    pub(crate) async fn acquire(self: &Arc<Self>, priority: Priority) -> Slot {
+        let rx = {
+            let mut state = self.state.lock().unwrap();
+            if !state.busy {
+                state.busy = true;
+                return Slot {
+                    scheduler: self.clone(),
+                };
+            }
+            let (tx, rx) = oneshot::channel();
+            let seq = state.next;
+            state.next += 1;
+            state.queue.push(Waiter { priority, seq, tx });
+            rx
+        };
+
+        let mut pending = Pending {
+            rx,
+            scheduler: self.clone(),
+            granted: false,
+        };
+        // the sender is only dropped after a successful send
+        let _ = (&mut pending.rx).await;
+        pending.granted = true;
+        Slot {
+            scheduler: self.clone(),
+        }
+    }
+
+    /// number of waiting requests, cancelled ones are only removed once the
+    /// slot is released
+    pub(crate) fn queued(&self) -> usize {
+        self.state.lock().unwrap().queue.len()
+    }
+
+    fn release(&self) {
+        let mut state = self.state.lock().unwrap();
+        while let Some(waiter) = state.queue.pop() {
+            // fails if the waiting request was cancelled in the meantime
+            if waiter.tx.send(()).is_ok() {
+                return;
+            }
+        }
+        state.busy = false;
+    }
+}
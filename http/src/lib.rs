@@ -1,9 +1,14 @@
+#[macro_use]
+mod trace;
 pub(crate) mod block;
 pub mod cache;
 mod client;
+pub mod connector;
+pub mod metrics;
 pub mod rate_limit;
 pub use client::*;
 pub use gw2lib_model as model;
+use gw2lib_model::authenticated::Permissions;
 use thiserror::Error;
 use tokio::sync::broadcast::error::RecvError;
 
@@ -16,6 +21,8 @@ use crate::{
 pub enum EndpointError {
     #[error("unsupported query type for this endpoint")]
     UnsupportedEndpointQuery,
+    #[error("no known endpoint serves {0}")]
+    UnknownEndpoint(String),
     #[error("endpoint requires authentication")]
     NotAuthenticated,
     #[error("unexpected rate limiting error")]
@@ -27,7 +34,39 @@ pub enum EndpointError {
     #[error("failed to retrieve item from already running request: {0}")]
     InflightReceiveFailed(#[from] RecvError),
     #[error("invalid json response: {0}")]
-    InvalidJsonResponse(#[from] serde_json::Error),
+    InvalidJsonResponse(Box<JsonError>),
+    #[error("invalid host: {0}")]
+    InvalidHost(String),
+    #[error("failed to build request: {0}")]
+    InvalidRequest(#[from] hyper::http::Error),
+    #[error("request timed out")]
+    Timeout,
+    #[error("failed to access rate limiter state: {0}")]
+    RateLimiterState(#[from] std::io::Error),
+    #[error("invalid query parameters: {0}")]
+    InvalidQuery(serde_urlencoded::ser::Error),
+    #[error("api key is missing the {0:?} permission")]
+    MissingPermission(Permissions),
+}
+
+/// describes where a response failed to deserialize
+#[derive(Error, Debug)]
+#[error("{endpoint} (schema {version}) at `{path}`{}: {source}", .id.as_ref().map(|id| format!(" of id {}", id)).unwrap_or_default())]
+pub struct JsonError {
+    /// url template of the endpoint
+    pub endpoint: &'static str,
+    /// schema version sent with the request
+    pub version: &'static str,
+    /// path to the failing field, e.g. `[17].details.type`
+    pub path: String,
+    /// id of the entity containing the failing field, if it has one
+    pub id: Option<String>,
+    pub source: serde_json::Error,
+    /// raw json of the failing entity, or the body around the error if the
+    /// entity couldn't be determined
+    ///
+    /// only set if enabled with [`Client::error_body`]
+    pub body: Option<String>,
 }
 
 #[derive(Error, Debug)]
@@ -40,4 +79,28 @@ pub enum ApiError {
     Other(hyper::StatusCode, String),
 }
 
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("invalid host: {0}")]
+    InvalidHost(String),
+    #[error("unsupported scheme: {0}")]
+    InvalidScheme(String),
+    #[error("{0}")]
+    InvalidLanguage(#[from] gw2lib_model::UnknownLanguage),
+    #[error("invalid api key")]
+    InvalidApiKey,
+    #[error("no api key configured")]
+    MissingApiKey,
+    #[error("invalid user agent: {0}")]
+    InvalidUserAgent(String),
+    #[error("invalid header: {0}")]
+    InvalidHeader(String),
+    #[error("invalid proxy: {0}")]
+    InvalidProxy(String),
+    #[error("{0} must be greater than zero")]
+    Zero(&'static str),
+    #[error("invalid value for {name}: {value}")]
+    InvalidValue { name: &'static str, value: String },
+}
+
 type EndpointResult<T> = Result<T, EndpointError>;
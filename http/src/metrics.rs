@@ -0,0 +1,286 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use hyper::StatusCode;
+
+/// receives measurements for every request the client makes
+///
+/// `endpoint` is the url template of the endpoint, e.g. `v2/items`. All
+/// methods are called inline, so implementations should return quickly.
+/// ## Example
+/// ```
+/// use std::sync::Arc;
+///
+/// use gw2lib::{metrics::InMemoryMetrics, Client};
+///
+/// let metrics = Arc::new(InMemoryMetrics::default());
+/// let mut client = Client::empty();
+/// client.metrics(metrics.clone());
+/// // serve this on your metrics endpoint
+/// let text = metrics.encode();
+/// ```
+pub trait Metrics: Send + Sync {
+    /// called after a request to the api finished
+    fn request(&self, endpoint: &'static str, status: StatusClass, latency: Duration) {
+        let _ = (endpoint, status, latency);
+    }
+
+    /// called for every cache lookup
+    fn cache(&self, endpoint: &'static str, hit: bool) {
+        let _ = (endpoint, hit);
+    }
+
+    /// called when a request had to wait for the rate limiter
+    fn rate_limit_wait(&self, endpoint: &'static str, wait: Duration) {
+        let _ = (endpoint, wait);
+    }
+
+    /// called when the api rejected a request with `429 Too Many Requests`
+    fn rate_limited(&self, endpoint: &'static str) {
+        let _ = endpoint;
+    }
+
+    /// called when ids are requested again because the in-flight request
+    /// they joined failed
+    fn retry(&self, endpoint: &'static str) {
+        let _ = endpoint;
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StatusClass {
+    Informational,
+    Success,
+    Redirection,
+    ClientError,
+    ServerError,
+    /// the request failed without a response, e.g. a timeout
+    Failed,
+}
+
+impl StatusClass {
+    const ALL: [StatusClass; 6] = [
+        StatusClass::Informational,
+        StatusClass::Success,
+        StatusClass::Redirection,
+        StatusClass::ClientError,
+        StatusClass::ServerError,
+        StatusClass::Failed,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StatusClass::Informational => "1xx",
+            StatusClass::Success => "2xx",
+            StatusClass::Redirection => "3xx",
+            StatusClass::ClientError => "4xx",
+            StatusClass::ServerError => "5xx",
+            StatusClass::Failed => "failed",
+        }
+    }
+}
+
+impl From<StatusCode> for StatusClass {
+    fn from(status: StatusCode) -> Self {
+        match status.as_u16() {
+            100..=199 => StatusClass::Informational,
+            200..=299 => StatusClass::Success,
+            300..=399 => StatusClass::Redirection,
+            400..=499 => StatusClass::ClientError,
+            _ => StatusClass::ServerError,
+        }
+    }
+}
+
+/// keeps counters per endpoint in memory
+///
+/// [`InMemoryMetrics::encode`] renders them in the prometheus text format
+#[derive(Default)]
+pub struct InMemoryMetrics {
+    endpoints: Mutex<BTreeMap<&'static str, Arc<Counters>>>,
+}
+
+#[derive(Default)]
+struct Counters {
+    requests: [AtomicU64; StatusClass::ALL.len()],
+    latency_micros: AtomicU64,
+    cache_hits: AtomicU64,
+    cache_misses: AtomicU64,
+    rate_limit_waits: AtomicU64,
+    rate_limit_wait_micros: AtomicU64,
+    rate_limited: AtomicU64,
+    retries: AtomicU64,
+}
+
+impl InMemoryMetrics {
+    fn counters(&self, endpoint: &'static str) -> Arc<Counters> {
+        self.endpoints
+            .lock()
+            .unwrap()
+            .entry(endpoint)
+            .or_default()
+            .clone()
+    }
+
+    /// renders all counters in the prometheus text exposition format
+    pub fn encode(&self) -> String {
+        let endpoints = self.endpoints.lock().unwrap().clone();
+        let mut out = String::new();
+
+        header(
+            &mut out,
+            "gw2lib_requests_total",
+            "Requests sent to the api",
+        );
+        for (endpoint, c) in &endpoints {
+            for (class, count) in StatusClass::ALL.iter().zip(&c.requests) {
+                let _ = writeln!(
+                    out,
+                    "gw2lib_requests_total{{endpoint=\"{}\",status=\"{}\"}} {}",
+                    endpoint,
+                    class.as_str(),
+                    count.load(Ordering::Relaxed)
+                );
+            }
+        }
+
+        header(
+            &mut out,
+            "gw2lib_request_duration_seconds_total",
+            "Time spent waiting for responses",
+        );
+        for (endpoint, c) in &endpoints {
+            let _ = writeln!(
+                out,
+                "gw2lib_request_duration_seconds_total{{endpoint=\"{}\"}} {}",
+                endpoint,
+                seconds(&c.latency_micros)
+            );
+        }
+
+        header(&mut out, "gw2lib_cache_lookups_total", "Cache lookups");
+        for (endpoint, c) in &endpoints {
+            for (result, count) in [("hit", &c.cache_hits), ("miss", &c.cache_misses)] {
+                let _ = writeln!(
+                    out,
+                    "gw2lib_cache_lookups_total{{endpoint=\"{}\",result=\"{}\"}} {}",
+                    endpoint,
+                    result,
+                    count.load(Ordering::Relaxed)
+                );
+            }
+        }
+
+        header(
+            &mut out,
+            "gw2lib_rate_limit_waits_total",
+            "Requests that had to wait for the rate limiter",
+        );
+        for (endpoint, c) in &endpoints {
+            let _ = writeln!(
+                out,
+                "gw2lib_rate_limit_waits_total{{endpoint=\"{}\"}} {}",
+                endpoint,
+                c.rate_limit_waits.load(Ordering::Relaxed)
+            );
+        }
+
+        header(
+            &mut out,
+            "gw2lib_rate_limit_wait_seconds_total",
+            "Time spent waiting for the rate limiter",
+        );
+        for (endpoint, c) in &endpoints {
+            let _ = writeln!(
+                out,
+                "gw2lib_rate_limit_wait_seconds_total{{endpoint=\"{}\"}} {}",
+                endpoint,
+                seconds(&c.rate_limit_wait_micros)
+            );
+        }
+
+        header(
+            &mut out,
+            "gw2lib_rate_limited_total",
+            "Requests rejected by the api with 429 Too Many Requests",
+        );
+        for (endpoint, c) in &endpoints {
+            let _ = writeln!(
+                out,
+                "gw2lib_rate_limited_total{{endpoint=\"{}\"}} {}",
+                endpoint,
+                c.rate_limited.load(Ordering::Relaxed)
+            );
+        }
+
+        header(
+            &mut out,
+            "gw2lib_retries_total",
+            "Ids requested again after the request they joined failed",
+        );
+        for (endpoint, c) in &endpoints {
+            let _ = writeln!(
+                out,
+                "gw2lib_retries_total{{endpoint=\"{}\"}} {}",
+                endpoint,
+                c.retries.load(Ordering::Relaxed)
+            );
+        }
+
+        out
+    }
+}
+
+impl Metrics for InMemoryMetrics {
+    fn request(&self, endpoint: &'static str, status: StatusClass, latency: Duration) {
+        let counters = self.counters(endpoint);
+        counters.requests[status as usize].fetch_add(1, Ordering::Relaxed);
+        counters
+            .latency_micros
+            .fetch_add(latency.as_micros() as u64, Ordering::Relaxed);
+    }
+
+    fn cache(&self, endpoint: &'static str, hit: bool) {
+        let counters = self.counters(endpoint);
+        let counter = if hit {
+            &counters.cache_hits
+        } else {
+            &counters.cache_misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn rate_limit_wait(&self, endpoint: &'static str, wait: Duration) {
+        let counters = self.counters(endpoint);
+        counters.rate_limit_waits.fetch_add(1, Ordering::Relaxed);
+        counters
+            .rate_limit_wait_micros
+            .fetch_add(wait.as_micros() as u64, Ordering::Relaxed);
+    }
+
+    fn rate_limited(&self, endpoint: &'static str) {
+        let counters = self.counters(endpoint);
+        counters.rate_limited.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn retry(&self, endpoint: &'static str) {
+        let counters = self.counters(endpoint);
+        counters.retries.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+fn seconds(micros: &AtomicU64) -> f64 {
+    micros.load(Ordering::Relaxed) as f64 / 1_000_000_f64
+}
+
+fn header(out: &mut String, name: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} counter", name);
+}
@@ -1,15 +1,43 @@
+use std::{
+    fs::{File, OpenOptions},
+    io::{Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
 use async_trait::async_trait;
 use chrono::{Duration, NaiveDateTime, Utc};
+use fs2::FileExt;
 
 use crate::EndpointError;
 
 #[async_trait]
 pub trait RateLimiter: Send {
     /// takes the amount of requests
-    /// returns the seconds to wait before executing them
-    async fn take(&mut self, num: usize) -> Result<u64, EndpointError>;
+    /// returns the time to wait before executing them
+    async fn take(&mut self, num: usize) -> Result<std::time::Duration, EndpointError>;
     /// incurs a penalty, indicating that the rate limit was hit
     async fn penalize(&mut self) -> Result<(), EndpointError>;
+    /// reports the remaining budget without taking from it
+    ///
+    /// rate limiters that don't track their budget report the default status
+    async fn status(&self) -> Result<RateLimitStatus, EndpointError> {
+        Ok(RateLimitStatus::default())
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RateLimitStatus {
+    /// requests that can be made right now without waiting
+    pub available: usize,
+    /// maximum number of requests in burst
+    pub capacity: usize,
+    /// when the next request becomes available, `None` if the bucket is full
+    pub next_refill: Option<NaiveDateTime>,
+    /// number of takes that had to wait
+    pub waits: u64,
+    /// time spent waiting across all takes
+    pub total_wait: std::time::Duration,
 }
 
 pub struct BucketRateLimiter {
@@ -18,19 +46,18 @@ pub struct BucketRateLimiter {
     /// requests per minute
     refill: usize,
     time: NaiveDateTime,
+    waits: Waits,
 }
 
 impl BucketRateLimiter {
     /// burst takes the maximum number of requests in burst
     /// refill sets the requests per minute
     pub fn new(burst: usize, refill: usize) -> Self {
-        let now = Utc::now().naive_utc();
-        let max = (60_f64 * 1000_f64 * (burst as f64) / (refill as f64)) as i64;
-        let base = now - Duration::milliseconds(max);
         Self {
             burst,
             refill,
-            time: base,
+            time: full_bucket(burst as f64, refill as f64),
+            waits: Default::default(),
         }
     }
 }
@@ -43,51 +70,360 @@ impl Default for BucketRateLimiter {
 
 #[async_trait]
 impl RateLimiter for BucketRateLimiter {
-    async fn take(&mut self, num: usize) -> Result<u64, EndpointError> {
-        let now = Utc::now().naive_utc();
-        let max = (60_f64 * 1000_f64 * (self.burst as f64) / (self.refill as f64)) as i64;
-        let base = now - Duration::milliseconds(max);
-        if self.time < base {
-            self.time = base;
+    async fn take(&mut self, num: usize) -> Result<std::time::Duration, EndpointError> {
+        let wait = take_bucket(&mut self.time, self.burst as f64, self.refill as f64, num);
+        Ok(self.waits.record(wait))
+    }
+
+    async fn penalize(&mut self) -> Result<(), EndpointError> {
+        penalize_bucket(&mut self.time, self.refill as f64);
+        Ok(())
+    }
+
+    async fn status(&self) -> Result<RateLimitStatus, EndpointError> {
+        Ok(bucket_status(
+            self.time,
+            self.burst,
+            self.refill as f64,
+            &self.waits,
+        ))
+    }
+}
+
+/// a bucket rate limiter that learns the refill rate from 429 responses
+///
+/// every penalty lowers the refill rate by a factor, every request taken
+/// afterwards raises it again by a fixed step until the configured maximum is
+/// reached (AIMD). Multiple services sharing one IP each converge to a share of
+/// the limit without tuning burst and refill by hand.
+/// ## Example
+/// ```
+/// use std::sync::Arc;
+///
+/// use gw2lib::{rate_limit::AdaptiveRateLimiter, Client};
+/// use tokio::sync::Mutex;
+///
+/// let rate_limiter = Arc::new(Mutex::new(AdaptiveRateLimiter::default()));
+/// let client = Client::empty().rate_limiter(rate_limiter.clone());
+/// ```
+pub struct AdaptiveRateLimiter {
+    /// maximum number of requests in burst
+    burst: usize,
+    /// current requests per minute
+    refill: f64,
+    min_refill: f64,
+    max_refill: f64,
+    /// requests per minute added for every request taken
+    increase: f64,
+    /// factor applied to the refill rate for every penalty
+    decrease: f64,
+    time: NaiveDateTime,
+    last_decrease: Option<NaiveDateTime>,
+    waits: Waits,
+}
+
+impl AdaptiveRateLimiter {
+    /// burst takes the maximum number of requests in burst
+    /// refill sets the maximum requests per minute
+    ///
+    /// the refill rate starts at its maximum, gets halved on every penalty
+    /// and recovers by one request per minute for every request taken. It
+    /// doesn't recover over time, an idle limiter keeps its lowered rate until
+    /// requests are made again.
+    ///
+    /// panics when `refill == 0`
+    pub fn new(burst: usize, refill: usize) -> Self {
+        assert!(refill > 0, "refill must be at least one request per minute");
+        let refill = refill as f64;
+        Self {
+            burst,
+            refill,
+            min_refill: 1.0,
+            max_refill: refill,
+            increase: 1.0,
+            decrease: 0.5,
+            time: full_bucket(burst as f64, refill),
+            last_decrease: None,
+            waits: Default::default(),
         }
-        let ratio = 60 * 1000 / self.refill as i64;
-        self.time += Duration::milliseconds(ratio * num as i64);
-        let sleep = self.time - now;
-        let millis = sleep.num_milliseconds();
-        // the api calculates the rate limit in intervals of 1s
-        // as a result, this code ensures that we never hit it at the cost of a bit of
-        // our burst
-        Ok(if millis < -1000 {
-            0
-        } else {
-            let millis = millis.abs();
-            let modulo = millis % 1000 != 0;
-            let ceil = millis / 1000 + (modulo as i64);
-            ceil as _
-        })
+    }
+
+    /// sets the minimum requests per minute, default is 1
+    pub fn min_refill(mut self, min: f64) -> Self {
+        self.min_refill = min.clamp(f64::MIN_POSITIVE, self.max_refill);
+        self.refill = self.refill.max(self.min_refill);
+        self
+    }
+
+    /// sets the requests per minute that get added for every request taken,
+    /// default is 1
+    ///
+    /// the rate only recovers with requests, not with the time passed
+    pub fn increase(mut self, step: f64) -> Self {
+        self.increase = step.max(0.0);
+        self
+    }
+
+    /// sets the factor that gets applied to the refill rate for every penalty,
+    /// default is 0.5
+    pub fn decrease(mut self, factor: f64) -> Self {
+        self.decrease = factor.clamp(0.0, 1.0);
+        self
+    }
+
+    /// returns the current estimated requests per minute
+    pub fn rate(&self) -> f64 {
+        self.refill
+    }
+}
+
+impl Default for AdaptiveRateLimiter {
+    fn default() -> Self {
+        Self::new(300, 300)
+    }
+}
+
+#[async_trait]
+impl RateLimiter for AdaptiveRateLimiter {
+    async fn take(&mut self, num: usize) -> Result<std::time::Duration, EndpointError> {
+        let wait = take_bucket(&mut self.time, self.burst as f64, self.refill, num);
+        self.refill = (self.refill + self.increase * num as f64).min(self.max_refill);
+        Ok(self.waits.record(wait))
     }
 
     async fn penalize(&mut self) -> Result<(), EndpointError> {
-        let ratio = 60 * 1000 / self.refill as i64;
         let now = Utc::now().naive_utc();
-        if self.time < now {
-            self.time = now;
+        // requests that are already in flight hit the limit as well, only the
+        // first of them should lower the rate
+        let cooldown = Duration::seconds(1);
+        if self
+            .last_decrease
+            .map_or(true, |last| now - last > cooldown)
+        {
+            self.refill = (self.refill * self.decrease).max(self.min_refill);
+            self.last_decrease = Some(now);
         }
-        // the api penalizes us for half a request worth of time when we hit it while
-        // rate limited
-        self.time += Duration::milliseconds(ratio / 2);
+        penalize_bucket(&mut self.time, self.refill);
         Ok(())
     }
+
+    async fn status(&self) -> Result<RateLimitStatus, EndpointError> {
+        Ok(bucket_status(
+            self.time,
+            self.burst,
+            self.refill,
+            &self.waits,
+        ))
+    }
+}
+
+/// a bucket rate limiter whose state is stored in a file, so that multiple
+/// processes on the same host share one request budget
+///
+/// the file is locked exclusively for every access. All processes using the
+/// same file should be configured with the same burst and refill. The waits
+/// reported by [`RateLimiter::status`] only cover this process.
+/// ## Example
+/// ```
+/// use std::sync::Arc;
+///
+/// use gw2lib::{rate_limit::FileRateLimiter, Client};
+/// use tokio::sync::Mutex;
+///
+/// let path = std::env::temp_dir().join("gw2lib-rate-limit");
+/// let rate_limiter = Arc::new(Mutex::new(FileRateLimiter::new(path, 300, 300)));
+/// let client = Client::empty().rate_limiter(rate_limiter.clone());
+/// ```
+pub struct FileRateLimiter {
+    path: Arc<PathBuf>,
+    /// maximum number of requests in burst
+    burst: usize,
+    /// requests per minute
+    refill: usize,
+    waits: Waits,
+}
+
+impl FileRateLimiter {
+    /// path is the file that holds the shared state, it gets created if it
+    /// does not exist
+    /// burst takes the maximum number of requests in burst
+    /// refill sets the requests per minute
+    pub fn new(path: impl Into<PathBuf>, burst: usize, refill: usize) -> Self {
+        Self {
+            path: Arc::new(path.into()),
+            burst,
+            refill,
+            waits: Default::default(),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// locks the file and updates the bucket time stored in it
+    async fn update<T: Send + 'static>(
+        &self,
+        f: impl FnOnce(&mut NaiveDateTime) -> T + Send + 'static,
+    ) -> Result<T, EndpointError> {
+        let path = self.path.clone();
+        let (burst, refill) = (self.burst as f64, self.refill as f64);
+        tokio::task::spawn_blocking(move || {
+            let mut file = OpenOptions::new()
+                .read(true)
+                .write(true)
+                .create(true)
+                .truncate(false)
+                .open(path.as_path())?;
+            file.lock_exclusive()?;
+            // the lock is released when the file gets closed
+            let mut time = read_bucket(&mut file)?.unwrap_or_else(|| full_bucket(burst, refill));
+            let res = f(&mut time);
+            write_bucket(&mut file, time)?;
+            Ok(res)
+        })
+        .await
+        .map_err(|_| EndpointError::RateLimiterCrashed)?
+    }
+}
+
+#[async_trait]
+impl RateLimiter for FileRateLimiter {
+    async fn take(&mut self, num: usize) -> Result<std::time::Duration, EndpointError> {
+        let (burst, refill) = (self.burst as f64, self.refill as f64);
+        let wait = self
+            .update(move |time| take_bucket(time, burst, refill, num))
+            .await?;
+        Ok(self.waits.record(wait))
+    }
+
+    async fn penalize(&mut self) -> Result<(), EndpointError> {
+        let refill = self.refill as f64;
+        self.update(move |time| penalize_bucket(time, refill)).await
+    }
+
+    async fn status(&self) -> Result<RateLimitStatus, EndpointError> {
+        let time = self.update(|time| *time).await?;
+        Ok(bucket_status(
+            time,
+            self.burst,
+            self.refill as f64,
+            &self.waits,
+        ))
+    }
+}
+
+/// the file contains the bucket time in microseconds since the unix epoch
+fn read_bucket(file: &mut File) -> std::io::Result<Option<NaiveDateTime>> {
+    let mut content = String::new();
+    file.seek(SeekFrom::Start(0))?;
+    file.read_to_string(&mut content)?;
+    Ok(content
+        .trim()
+        .parse()
+        .ok()
+        .and_then(chrono::DateTime::from_timestamp_micros)
+        .map(|time| time.naive_utc()))
+}
+
+fn write_bucket(file: &mut File, time: NaiveDateTime) -> std::io::Result<()> {
+    file.set_len(0)?;
+    file.seek(SeekFrom::Start(0))?;
+    write!(file, "{}", time.and_utc().timestamp_micros())?;
+    file.flush()
 }
 
 pub struct NoopRateLimiter;
 #[async_trait]
 impl RateLimiter for NoopRateLimiter {
-    async fn take(&mut self, _num: usize) -> Result<u64, EndpointError> {
-        Ok(0)
+    async fn take(&mut self, _num: usize) -> Result<std::time::Duration, EndpointError> {
+        Ok(std::time::Duration::ZERO)
     }
 
     async fn penalize(&mut self) -> Result<(), EndpointError> {
         Ok(())
     }
+
+    async fn status(&self) -> Result<RateLimitStatus, EndpointError> {
+        Ok(RateLimitStatus {
+            available: usize::MAX,
+            capacity: usize::MAX,
+            ..Default::default()
+        })
+    }
+}
+
+#[derive(Default)]
+struct Waits {
+    count: u64,
+    total: std::time::Duration,
+}
+
+impl Waits {
+    fn record(&mut self, wait: std::time::Duration) -> std::time::Duration {
+        if !wait.is_zero() {
+            self.count += 1;
+            self.total += wait;
+        }
+        wait
+    }
+}
+
+/// microseconds it takes to refill one request
+fn interval(refill: f64) -> f64 {
+    60_f64 * 1_000_000_f64 / refill
+}
+
+/// returns the bucket time of a full bucket
+fn full_bucket(burst: f64, refill: f64) -> NaiveDateTime {
+    let now = Utc::now().naive_utc();
+    now - Duration::microseconds((burst * interval(refill)) as i64)
+}
+
+/// `time` is the point in time at which the bucket is empty
+fn take_bucket(
+    time: &mut NaiveDateTime,
+    burst: f64,
+    refill: f64,
+    num: usize,
+) -> std::time::Duration {
+    let now = Utc::now().naive_utc();
+    let base = now - Duration::microseconds((burst * interval(refill)) as i64);
+    if *time < base {
+        *time = base;
+    }
+    *time += Duration::microseconds((interval(refill) * num as f64) as i64);
+    // negative durations mean that the requests are covered by our burst
+    (*time - now).to_std().unwrap_or_default()
+}
+
+fn penalize_bucket(time: &mut NaiveDateTime, refill: f64) {
+    let now = Utc::now().naive_utc();
+    if *time < now {
+        *time = now;
+    }
+    // the api penalizes us for half a request worth of time when we hit it while
+    // rate limited
+    *time += Duration::microseconds((interval(refill) / 2.0) as i64);
+}
+
+fn bucket_status(time: NaiveDateTime, burst: usize, refill: f64, waits: &Waits) -> RateLimitStatus {
+    let now = Utc::now().naive_utc();
+    let interval = interval(refill);
+    let tokens = (now - time).num_microseconds().unwrap_or(i64::MAX) as f64 / interval;
+    let (available, next_refill) = if tokens >= burst as f64 {
+        (burst, None)
+    } else {
+        // the bucket might be in debt after a penalty
+        let next = (tokens.floor() + 1.0).max(1.0);
+        let next_refill = time + Duration::microseconds((next * interval) as i64);
+        (tokens.max(0.0) as usize, Some(next_refill))
+    };
+    RateLimitStatus {
+        available,
+        capacity: burst,
+        next_refill,
+        waits: waits.count,
+        total_wait: waits.total,
+    }
 }
@@ -1,5 +1,6 @@
 #![cfg(feature = "blocking")]
 
+use chrono::{TimeZone, Utc};
 use gw2lib::{model::authenticated::account::Account, Requester};
 
 pub mod setup;
@@ -9,3 +10,19 @@ fn eff_testing() {
     let client = setup::setup();
     let _: Account = client.get().unwrap();
 }
+
+#[test]
+fn timestamps() {
+    let json = r#"{"id":"C19E4E5D-3D0B-4E3F-9D4B-0A4B1F0C7E3B","age":3600,"name":"Account.1234","world":1001,"guilds":[],"guild_leader":null,"created":"2013-04-23T08:27:00Z","access":["GuildWars2"],"commander":false,"fractal_level":null,"daily_ap":null,"monthly_ap":null,"wvw_rank":null,"last_modified":"2022-07-25T17:31:42.125Z"}"#;
+    let account: Account = serde_json::from_str(json).unwrap();
+    assert_eq!(
+        account.created,
+        Utc.with_ymd_and_hms(2013, 4, 23, 8, 27, 0).unwrap()
+    );
+    assert!(account.created < account.last_modified);
+
+    let round_trip: Account =
+        serde_json::from_str(&serde_json::to_string(&account).unwrap()).unwrap();
+    assert_eq!(round_trip.created, account.created);
+    assert_eq!(round_trip.last_modified, account.last_modified);
+}
@@ -0,0 +1,95 @@
+#![cfg(feature = "blocking")]
+
+use std::time::Duration;
+
+use gw2lib::{Client, ClientConfig, ConfigError, Scheme};
+
+#[test]
+fn default_host() {
+    let client = Client::builder().build().unwrap();
+    assert_eq!(client.host, "https://api.guildwars2.com");
+}
+
+#[test]
+fn host_with_scheme() {
+    let client = Client::builder()
+        .host("http://localhost:52321/")
+        .build()
+        .unwrap();
+    assert_eq!(client.host, "http://localhost:52321");
+}
+
+#[test]
+fn separate_scheme() {
+    let client = Client::builder()
+        .host("localhost:52321")
+        .scheme(Scheme::Http)
+        .build()
+        .unwrap();
+    assert_eq!(client.host, "http://localhost:52321");
+}
+
+#[test]
+fn invalid_host() {
+    let res = Client::builder().host("https://localhost/v2").build();
+    assert!(matches!(res, Err(ConfigError::InvalidHost(_))));
+    let res = Client::builder().host("ftp://localhost").build();
+    assert!(matches!(res, Err(ConfigError::InvalidScheme(_))));
+    let res = Client::builder()
+        .host("https://localhost")
+        .scheme(Scheme::Http)
+        .build();
+    assert!(matches!(res, Err(ConfigError::InvalidScheme(_))));
+}
+
+#[test]
+fn api_key() {
+    let res = Client::builder().build_authenticated();
+    assert!(matches!(res, Err(ConfigError::MissingApiKey)));
+    let res = Client::builder()
+        .api_key("invalid key")
+        .build_authenticated();
+    assert!(matches!(res, Err(ConfigError::InvalidApiKey)));
+    let res = Client::builder().api_key("ABCD-1234").build_authenticated();
+    assert!(res.is_ok());
+}
+
+#[test]
+fn zero_values() {
+    let res = Client::builder().max_concurrent_requests(0).build();
+    assert!(matches!(res, Err(ConfigError::Zero(_))));
+    let res = Client::builder().timeout(Duration::ZERO).build();
+    assert!(matches!(res, Err(ConfigError::Zero(_))));
+}
+
+#[test]
+fn config() {
+    let config = ClientConfig {
+        host: Some("localhost:52321".to_string()),
+        scheme: Some(Scheme::Http),
+        language: Some("de".to_string()),
+        ..Default::default()
+    };
+    let client = Client::builder().config(config).unwrap().build().unwrap();
+    assert_eq!(client.host, "http://localhost:52321");
+    assert_eq!(client.language, gw2lib::model::Language::De);
+
+    let config = ClientConfig {
+        language: Some("xx".to_string()),
+        ..Default::default()
+    };
+    assert!(matches!(
+        Client::builder().config(config),
+        Err(ConfigError::InvalidLanguage(_))
+    ));
+}
+
+#[test]
+fn headers() {
+    let res = Client::builder().header("X-Trace", "abc").build();
+    assert!(res.is_ok());
+    let res = Client::builder().header("invalid header", "abc").build();
+    assert!(matches!(res, Err(ConfigError::InvalidHeader(_))));
+    let res = Client::builder().user_agent("tool\n").build();
+    assert!(matches!(res, Err(ConfigError::InvalidUserAgent(_))));
+}
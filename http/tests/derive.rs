@@ -0,0 +1,57 @@
+#![cfg(feature = "blocking")]
+
+use std::sync::{Arc, Mutex};
+
+use gw2lib::{
+    model::{BulkEndpoint, Endpoint},
+    Client, Requester,
+};
+use serde::Deserialize;
+
+pub mod setup;
+
+/// an endpoint defined outside of the model crate
+#[derive(Clone, Debug, Deserialize, Endpoint)]
+#[endpoint(
+    crate = "gw2lib::model",
+    url = "v2/dungeons",
+    version = "2022-07-22T00:00:00.000Z",
+    locale,
+    bulk(all = false),
+    id = "id"
+)]
+struct Dungeon {
+    id: String,
+}
+
+/// answers every request with one dungeon and records the request lines
+fn api(requests: Arc<Mutex<Vec<String>>>) -> u16 {
+    setup::mock_api(move |request| {
+        requests.lock().unwrap().push(request.line.clone());
+        (200, r#"[{"id":"ascalonian_catacombs"}]"#.to_string())
+    })
+}
+
+#[test]
+fn derived_endpoint() {
+    assert_eq!(
+        (Dungeon::AUTHENTICATED, Dungeon::LOCALE, Dungeon::ALL),
+        (false, true, false)
+    );
+
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let api = api(requests.clone());
+    let client = Client::empty().host_http(format!("http://127.0.0.1:{}", api));
+
+    let dungeons: Vec<Dungeon> = client
+        .many(vec!["ascalonian_catacombs".to_string()])
+        .unwrap();
+    assert_eq!(dungeons[0].id(), "ascalonian_catacombs");
+
+    let requests = requests.lock().unwrap();
+    assert!(
+        requests[0].starts_with("GET /v2/dungeons?lang=en&ids=ascalonian_catacombs "),
+        "{}",
+        requests[0]
+    );
+}
@@ -0,0 +1,110 @@
+#![cfg(feature = "blocking")]
+
+use gw2lib::{
+    model::misc::worlds::{World, WorldId},
+    Client, EndpointError, Requester,
+};
+
+pub mod setup;
+
+/// answers every request with `body`
+fn api(body: &'static str) -> u16 {
+    setup::mock_api(move |_| (200, body.to_string()))
+}
+
+#[test]
+fn failing_entity() {
+    let api = api(
+        r#"[{"id":1001,"name":"Anvil Rock","population":"High"},{"id":1002,"name":"Borlis Pass","population":5}]"#,
+    );
+    let mut client = Client::empty().host_http(format!("http://127.0.0.1:{}", api));
+    client.error_body(true);
+
+    let err = client
+        .many::<World, _>(vec![WorldId(1001), WorldId(1002)])
+        .unwrap_err();
+    let EndpointError::InvalidJsonResponse(err) = err else {
+        panic!("unexpected error: {}", err);
+    };
+    assert_eq!(err.endpoint, "v2/worlds");
+    assert_eq!(err.path, "[1].population");
+    assert_eq!(err.id.as_deref(), Some("1002"));
+    assert_eq!(
+        err.body.as_deref(),
+        Some(r#"{"id":1002,"name":"Borlis Pass","population":5}"#)
+    );
+    assert!(err.to_string().contains("of id 1002"), "{}", err);
+}
+
+#[test]
+fn invalid_json() {
+    let api = api(r#"{"id":1001,"name":"Anvil Rock","#);
+    let client = Client::empty().host_http(format!("http://127.0.0.1:{}", api));
+
+    let err = client.single::<World, _>(WorldId(1001)).unwrap_err();
+    let EndpointError::InvalidJsonResponse(err) = err else {
+        panic!("unexpected error: {}", err);
+    };
+    assert_eq!(err.id, None);
+    assert_eq!(err.body, None);
+}
+
+#[test]
+fn lenient() {
+    let api = api(
+        r#"[{"id":1001,"name":"Anvil Rock","population":"High"},{"id":1002,"name":"Borlis Pass","population":5}]"#,
+    );
+    let client = Client::empty().host_http(format!("http://127.0.0.1:{}", api));
+
+    let (worlds, failures) = client
+        .many_lenient::<World, _>(vec![WorldId(1001), WorldId(1002)])
+        .unwrap();
+    assert_eq!(worlds.len(), 1);
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].path, "[1].population");
+    assert_eq!(failures[0].id.as_deref(), Some("1002"));
+    assert_eq!(failures[0].body, None);
+
+    let mut client = Client::empty().host_http(format!("http://127.0.0.1:{}", api));
+    client.error_body(true);
+    let (_, failures) = client
+        .many_lenient::<World, _>(vec![WorldId(1001), WorldId(1002)])
+        .unwrap();
+    assert_eq!(
+        failures[0].body.as_deref(),
+        Some(r#"{"id":1002,"name":"Borlis Pass","population":5}"#)
+    );
+}
+
+#[test]
+fn lenient_inflight() {
+    // slow enough for the second request to join the first one
+    let api = setup::mock_api(|request| {
+        std::thread::sleep(std::time::Duration::from_millis(300));
+        let (_, ids) = request.path().split_once("ids=").unwrap();
+        let worlds: Vec<_> = ids
+            .split(',')
+            .map(|id| match id {
+                "1001" => r#"{"id":1001,"name":"Anvil Rock","population":"High"}"#,
+                _ => r#"{"id":1002,"name":"Borlis Pass","population":5}"#,
+            })
+            .collect();
+        (200, format!("[{}]", worlds.join(",")))
+    });
+    let client = Client::empty().host_http(format!("http://127.0.0.1:{}", api));
+
+    std::thread::scope(|s| {
+        let first = s.spawn(|| client.many_lenient::<World, _>(vec![WorldId(1001), WorldId(1002)]));
+        std::thread::sleep(std::time::Duration::from_millis(100));
+        let (worlds, failures) = client
+            .many_lenient::<World, _>(vec![WorldId(1002)])
+            .unwrap();
+        assert!(worlds.is_empty());
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].id.as_deref(), Some("1002"));
+
+        let (worlds, failures) = first.join().unwrap().unwrap();
+        assert_eq!(worlds.len(), 1);
+        assert_eq!(failures.len(), 1);
+    });
+}
@@ -0,0 +1,94 @@
+#![cfg(feature = "blocking")]
+
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use gw2lib::{
+    cache::InMemoryCache,
+    metrics::InMemoryMetrics,
+    model::misc::worlds::{World, WorldId},
+    ApiError, Client, EndpointError, Requester,
+};
+
+pub mod setup;
+
+/// answers every request with a world
+fn api() -> u16 {
+    setup::mock_api(|_| {
+        let body = r#"{"id":1001,"name":"Anvil Rock","population":"High"}"#;
+        (200, body.to_string())
+    })
+}
+
+#[test]
+fn in_memory() {
+    let api = api();
+    let metrics = Arc::new(InMemoryMetrics::default());
+    let mut client = Client::empty()
+        .cache(InMemoryCache::default())
+        .host_http(format!("http://127.0.0.1:{}", api));
+    client.metrics(metrics.clone());
+
+    let _: World = client.single(WorldId(1001)).unwrap();
+    let _: World = client.single(WorldId(1001)).unwrap();
+
+    let text = metrics.encode();
+    assert!(text.contains("# TYPE gw2lib_requests_total counter"));
+    assert!(text.contains(r#"gw2lib_requests_total{endpoint="v2/worlds",status="2xx"} 1"#));
+    assert!(text.contains(r#"gw2lib_requests_total{endpoint="v2/worlds",status="4xx"} 0"#));
+    assert!(text.contains(r#"gw2lib_cache_lookups_total{endpoint="v2/worlds",result="hit"} 1"#));
+    assert!(text.contains(r#"gw2lib_rate_limit_waits_total{endpoint="v2/worlds"} 0"#));
+    assert!(text.contains(r#"gw2lib_rate_limited_total{endpoint="v2/worlds"} 0"#));
+    assert!(text.contains(r#"gw2lib_retries_total{endpoint="v2/worlds"} 0"#));
+}
+
+#[test]
+fn rate_limited() {
+    let api = setup::mock_api(|_| (429, r#"{"text":"too many requests"}"#.to_string()));
+    let metrics = Arc::new(InMemoryMetrics::default());
+    let mut client = Client::empty().host_http(format!("http://127.0.0.1:{}", api));
+    client.metrics(metrics.clone());
+
+    let err = client.single::<World, _>(WorldId(1001)).unwrap_err();
+    assert!(matches!(
+        err,
+        EndpointError::ApiError(ApiError::RateLimited)
+    ));
+
+    let text = metrics.encode();
+    assert!(text.contains(r#"gw2lib_requests_total{endpoint="v2/worlds",status="4xx"} 1"#));
+    assert!(text.contains(r#"gw2lib_rate_limited_total{endpoint="v2/worlds"} 1"#));
+}
+
+#[test]
+fn retry() {
+    // the first request fails slowly enough for the second one to join it
+    let requests = AtomicUsize::new(0);
+    let api = setup::mock_api(move |_| {
+        if requests.fetch_add(1, Ordering::SeqCst) == 0 {
+            std::thread::sleep(Duration::from_millis(300));
+            return (500, r#"{"text":"internal error"}"#.to_string());
+        }
+        let body = r#"[{"id":1001,"name":"Anvil Rock","population":"High"}]"#;
+        (200, body.to_string())
+    });
+    let metrics = Arc::new(InMemoryMetrics::default());
+    let mut client = Client::empty().host_http(format!("http://127.0.0.1:{}", api));
+    client.metrics(metrics.clone());
+
+    std::thread::scope(|s| {
+        let first = s.spawn(|| client.many::<World, _>(vec![WorldId(1001)]));
+        std::thread::sleep(Duration::from_millis(100));
+        let worlds = client.many::<World, _>(vec![WorldId(1001)]).unwrap();
+        assert_eq!(worlds.len(), 1);
+        assert!(first.join().unwrap().is_err());
+    });
+
+    let text = metrics.encode();
+    assert!(text.contains(r#"gw2lib_retries_total{endpoint="v2/worlds"} 1"#));
+}
@@ -0,0 +1,90 @@
+#![cfg(feature = "blocking")]
+
+use std::sync::{Arc, Mutex};
+
+use gw2lib::{
+    cache::InMemoryCache,
+    model::misc::continents::{ContinentId, Floor, FloorPath, Region, RegionId, RegionPath},
+    Client, EndpointError, Requester,
+};
+
+pub mod setup;
+
+fn region(id: &str) -> String {
+    format!(
+        r#"{{"name":"Shiverpeaks","label_coord":[1.0,2.0],"continent_rect":[[0.0,0.0],[1.0,1.0]],"maps":{{}},"id":{id}}}"#
+    )
+}
+
+/// serves floors and regions and records the request lines
+fn api(requests: Arc<Mutex<Vec<String>>>) -> u16 {
+    setup::mock_api(move |request| {
+        let path = request.path().to_string();
+        requests.lock().unwrap().push(path.clone());
+        let body = if let Some((_, ids)) = path.split_once("&ids=") {
+            let regions: Vec<_> = ids.split(',').map(region).collect();
+            format!("[{}]", regions.join(","))
+        } else if path.contains("/floors?") {
+            "[0,1]".to_string()
+        } else {
+            format!(
+                r#"{{"texture_dims":[1,2],"regions":{{"2":{}}},"id":1}}"#,
+                region("2")
+            )
+        };
+        (200, body)
+    })
+}
+
+#[test]
+fn nested_endpoints() {
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let api = api(requests.clone());
+    let client = Client::empty()
+        .cache(InMemoryCache::default())
+        .host_http(format!("http://127.0.0.1:{}", api));
+
+    let floors = client.nested_ids::<Floor, _, _>(ContinentId(1)).unwrap();
+    assert_eq!(floors, vec![FloorPath::new(1, 0), FloorPath::new(1, 1)]);
+
+    let floor: Floor = client.single(FloorPath::new(1, 1)).unwrap();
+    assert_eq!(floor.id, FloorPath::new(1, 1));
+    assert_eq!(
+        floor.regions[&RegionId(2)].id,
+        RegionPath::new(FloorPath::new(1, 1), 2)
+    );
+
+    let ids = vec![
+        RegionPath::new(FloorPath::new(1, 1), 2),
+        RegionPath::new(FloorPath::new(1, 1), 3),
+        RegionPath::new(FloorPath::new(2, 1), 2),
+    ];
+    let mut regions: Vec<Region> = client.many(ids.clone()).unwrap();
+    regions.sort_by_key(|r| r.id);
+    assert_eq!(regions.iter().map(|r| r.id).collect::<Vec<_>>(), ids);
+
+    // cached per parent
+    let _: Region = client.single(ids[2]).unwrap();
+
+    let mut requests = requests.lock().unwrap().clone();
+    requests[2..].sort();
+    assert_eq!(
+        requests,
+        vec![
+            "/v2/continents/1/floors?lang=en",
+            "/v2/continents/1/floors/1?lang=en",
+            "/v2/continents/1/floors/1/regions?lang=en&ids=2,3",
+            "/v2/continents/2/floors/1/regions?lang=en&ids=2",
+        ]
+    );
+}
+
+#[test]
+fn missing_parent() {
+    let client = Client::empty().host_http("http://127.0.0.1:1");
+    let result = client.ids::<Floor, FloorPath>();
+    assert!(matches!(
+        result,
+        Err(EndpointError::UnsupportedEndpointQuery)
+    ));
+}
@@ -0,0 +1,55 @@
+#![cfg(feature = "blocking")]
+
+use std::sync::{Arc, Mutex};
+
+use gw2lib::{
+    model::authenticated::{
+        account::{wallet::Wallet, Account},
+        Permissions,
+    },
+    Client, EndpointError, Requester,
+};
+
+pub mod setup;
+
+/// answers tokeninfo with the account permission only, and records the
+/// request lines
+fn api(requests: Arc<Mutex<Vec<String>>>) -> u16 {
+    setup::mock_api(move |request| {
+        let body = if request.path().starts_with("/v2/tokeninfo") {
+            r#"{"id":"key","name":"test","permissions":["account"],"type":"APIKey"}"#
+        } else {
+            r#"{"id":"C19E4E5D-3D0B-4E3F-9D4B-0A4B1F0C7E3B","age":3600,"name":"Account.1234","world":1001,"guilds":[],"guild_leader":null,"created":"2013-04-23T08:27:00Z","access":["GuildWars2"],"commander":false,"fractal_level":null,"daily_ap":null,"monthly_ap":null,"wvw_rank":null,"last_modified":"2022-07-25T17:31:42.125Z"}"#
+        };
+        requests.lock().unwrap().push(request.line.clone());
+        (200, body.to_string())
+    })
+}
+
+#[test]
+fn missing_permission() {
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let api = api(requests.clone());
+    let client = Client::empty()
+        .host_http(format!("http://127.0.0.1:{}", api))
+        .api_key("key");
+
+    let err = client.get::<Wallet>().unwrap_err();
+    assert!(
+        matches!(err, EndpointError::MissingPermission(Permissions::Wallet)),
+        "{}",
+        err
+    );
+    let _: Account = client.get().unwrap();
+
+    // tokeninfo is only requested once, the wallet never
+    let paths = requests.lock().unwrap().clone();
+    assert_eq!(paths.len(), 2);
+    assert!(paths[0].starts_with("GET /v2/tokeninfo"), "{}", paths[0]);
+    assert!(paths[1].starts_with("GET /v2/account"), "{}", paths[1]);
+
+    // a new key requests its permissions again
+    let client = client.api_key("other key");
+    let _: Account = client.get().unwrap();
+    assert_eq!(requests.lock().unwrap().len(), 4);
+}
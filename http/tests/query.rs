@@ -0,0 +1,46 @@
+#![cfg(feature = "blocking")]
+
+use std::sync::{Arc, Mutex};
+
+use gw2lib::{
+    cache::InMemoryCache,
+    model::items::recipes::{RecipeId, RecipeSearch, RecipeSearchQuery},
+    Client, Requester,
+};
+
+pub mod setup;
+
+/// answers every request with two recipe ids and records the request lines
+fn api(requests: Arc<Mutex<Vec<String>>>) -> u16 {
+    setup::mock_api(move |request| {
+        requests.lock().unwrap().push(request.line.clone());
+        (200, "[7314,7315]".to_string())
+    })
+}
+
+#[test]
+fn cached_per_query() {
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let api = api(requests.clone());
+    let client = Client::empty()
+        .cache(InMemoryCache::default())
+        .host_http(format!("http://127.0.0.1:{}", api));
+
+    let recipes: RecipeSearch = client.query(&RecipeSearchQuery::output(19721)).unwrap();
+    assert_eq!(recipes.0, vec![RecipeId(7314), RecipeId(7315)]);
+    let _: RecipeSearch = client.query(&RecipeSearchQuery::output(19721)).unwrap();
+    let _: RecipeSearch = client.query(&RecipeSearchQuery::input(19721)).unwrap();
+
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests.len(), 2);
+    assert!(
+        requests[0].starts_with("GET /v2/recipes/search?output=19721 "),
+        "{}",
+        requests[0]
+    );
+    assert!(
+        requests[1].starts_with("GET /v2/recipes/search?input=19721 "),
+        "{}",
+        requests[1]
+    );
+}
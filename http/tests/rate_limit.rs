@@ -0,0 +1,188 @@
+#![cfg(feature = "blocking")]
+
+use std::{sync::Arc, thread, time::Duration};
+
+use async_trait::async_trait;
+use gw2lib::{
+    model::misc::worlds::{World, WorldId},
+    rate_limit::{AdaptiveRateLimiter, BucketRateLimiter, FileRateLimiter, RateLimiter},
+    Client, EndpointError, Priority, Requester,
+};
+use tokio::sync::{oneshot, Mutex};
+
+pub mod setup;
+
+fn block<F: std::future::Future>(fut: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(fut)
+}
+
+#[test]
+fn bucket_precision() {
+    block(async {
+        // 2 burst, 1 request per second
+        let mut limiter = BucketRateLimiter::new(2, 60);
+        assert_eq!(limiter.take(1).await.unwrap(), Duration::ZERO);
+        assert_eq!(limiter.take(1).await.unwrap(), Duration::ZERO);
+        let wait = limiter.take(1).await.unwrap();
+        assert!(wait > Duration::from_millis(900), "{:?}", wait);
+        assert!(wait <= Duration::from_secs(1), "{:?}", wait);
+        let wait = limiter.take(2).await.unwrap();
+        assert!(wait > Duration::from_millis(2900), "{:?}", wait);
+        assert!(wait <= Duration::from_secs(3), "{:?}", wait);
+    });
+}
+
+#[test]
+fn bucket_status() {
+    block(async {
+        let mut limiter = BucketRateLimiter::new(2, 60);
+        let status = limiter.status().await.unwrap();
+        assert_eq!(status.available, 2);
+        assert_eq!(status.capacity, 2);
+        assert_eq!(status.next_refill, None);
+
+        limiter.take(2).await.unwrap();
+        let status = limiter.status().await.unwrap();
+        assert_eq!(status.available, 0);
+        assert!(status.next_refill.is_some());
+        assert_eq!(status.waits, 0);
+
+        let wait = limiter.take(1).await.unwrap();
+        let status = limiter.status().await.unwrap();
+        assert_eq!(status.waits, 1);
+        assert_eq!(status.total_wait, wait);
+    });
+}
+
+#[test]
+fn adaptive_backoff() {
+    block(async {
+        let mut limiter = AdaptiveRateLimiter::new(10, 100).increase(10.0);
+        assert_eq!(limiter.rate(), 100.0);
+        limiter.penalize().await.unwrap();
+        assert_eq!(limiter.rate(), 50.0);
+        // simultaneous 429s only count once
+        limiter.penalize().await.unwrap();
+        assert_eq!(limiter.rate(), 50.0);
+        limiter.take(2).await.unwrap();
+        assert_eq!(limiter.rate(), 70.0);
+        limiter.take(5).await.unwrap();
+        assert_eq!(limiter.rate(), 100.0);
+    });
+}
+
+#[test]
+fn adaptive_min_refill() {
+    let limiter = AdaptiveRateLimiter::new(10, 100).min_refill(500.0);
+    assert_eq!(limiter.rate(), 100.0);
+    let limiter = AdaptiveRateLimiter::new(10, 1).min_refill(0.0);
+    assert_eq!(limiter.rate(), 1.0);
+}
+
+#[test]
+#[should_panic(expected = "refill must be at least one request per minute")]
+fn adaptive_zero_refill() {
+    let _ = AdaptiveRateLimiter::new(10, 0);
+}
+
+#[test]
+fn shared_file() {
+    let path = std::env::temp_dir().join(format!("gw2lib-rate-limit-{}", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    block(async {
+        // two independent limiters, as if they were in different processes
+        let mut a = FileRateLimiter::new(&path, 2, 60);
+        let mut b = FileRateLimiter::new(&path, 2, 60);
+        assert_eq!(a.take(1).await.unwrap(), Duration::ZERO);
+        assert_eq!(b.take(1).await.unwrap(), Duration::ZERO);
+        let wait = a.take(1).await.unwrap();
+        assert!(wait > Duration::from_millis(900), "{:?}", wait);
+        let wait = b.take(1).await.unwrap();
+        assert!(wait > Duration::from_millis(1900), "{:?}", wait);
+    });
+    std::fs::remove_file(&path).unwrap();
+}
+
+/// answers every request with a world
+fn api() -> u16 {
+    setup::mock_api(|_| {
+        let body = r#"{"id":1001,"name":"Anvil Rock","population":"High"}"#;
+        (200, body.to_string())
+    })
+}
+
+/// records the threads taking from it, the first take blocks until released
+struct Recorder {
+    order: Arc<std::sync::Mutex<Vec<String>>>,
+    release: Option<oneshot::Receiver<()>>,
+}
+
+#[async_trait]
+impl RateLimiter for Recorder {
+    async fn take(&mut self, _num: usize) -> Result<Duration, EndpointError> {
+        let name = thread::current().name().unwrap_or_default().to_string();
+        self.order.lock().unwrap().push(name);
+        if let Some(release) = self.release.take() {
+            let _ = release.await;
+        }
+        Ok(Duration::ZERO)
+    }
+
+    async fn penalize(&mut self) -> Result<(), EndpointError> {
+        Ok(())
+    }
+}
+
+fn wait_until(condition: impl Fn() -> bool) {
+    while !condition() {
+        thread::sleep(Duration::from_millis(1));
+    }
+}
+
+#[test]
+fn priority() {
+    let api = api();
+    let order = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let (release, rx) = oneshot::channel();
+    let recorder = Recorder {
+        order: order.clone(),
+        release: Some(rx),
+    };
+    let client = Client::empty()
+        .host_http(format!("http://127.0.0.1:{}", api))
+        .rate_limiter(Arc::new(Mutex::new(recorder)));
+
+    thread::scope(|s| {
+        let request = |name: &str, priority, id| {
+            let client = &client;
+            thread::Builder::new()
+                .name(name.to_string())
+                .spawn_scoped(s, move || {
+                    let _: World = client.priority(priority).single(id).unwrap();
+                })
+                .unwrap();
+        };
+
+        // holds the slot until the others are queued
+        request("first", Priority::Normal, WorldId(1001));
+        wait_until(|| order.lock().unwrap().len() == 1);
+        let waiting = [
+            ("low1", Priority::Low, WorldId(1002)),
+            ("low2", Priority::Low, WorldId(1003)),
+            ("high", Priority::High, WorldId(1004)),
+        ];
+        for (queued, (name, priority, id)) in waiting.into_iter().enumerate() {
+            request(name, priority, id);
+            wait_until(|| client.queued_requests() == queued + 1);
+        }
+        release.send(()).unwrap();
+    });
+
+    let order = order.lock().unwrap();
+    assert_eq!(*order, ["first", "high", "low1", "low2"]);
+    assert_eq!(client.queued_requests(), 0);
+}
@@ -0,0 +1,132 @@
+#![cfg(feature = "blocking")]
+
+use std::sync::{Arc, Mutex};
+
+use gw2lib::{
+    cache::InMemoryCache,
+    metrics::InMemoryMetrics,
+    model::{
+        items::Item,
+        registry::{self, EndpointKind},
+        Endpoint,
+    },
+    Client, EndpointError, Requester,
+};
+
+pub mod setup;
+
+/// answers every request with two items and records the request lines and
+/// schema versions
+fn api(requests: Arc<Mutex<Vec<(String, String)>>>) -> u16 {
+    setup::mock_api(move |request| {
+        let version = request.header("x-schema-version").unwrap_or_default();
+        requests
+            .lock()
+            .unwrap()
+            .push((request.line.clone(), version.to_uppercase()));
+        let body = r#"[{"id":19721,"name":"Glob of Ectoplasm"},{"id":19993}]"#;
+        (200, body.to_string())
+    })
+}
+
+#[test]
+fn raw() {
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let api = api(requests.clone());
+    let metrics = Arc::new(InMemoryMetrics::default());
+    let mut client = Client::empty()
+        .cache(InMemoryCache::default())
+        .host_http(format!("http://127.0.0.1:{}", api));
+    client.metrics(metrics.clone());
+
+    let items = client.raw("v2/items?ids=19721,19993").unwrap();
+    assert_eq!(items[0]["name"], "Glob of Ectoplasm");
+    // cached per path
+    let _ = client.raw("v2/items?ids=19721,19993").unwrap();
+
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests.len(), 1);
+    assert!(
+        requests[0]
+            .0
+            .starts_with("GET /v2/items?lang=en&ids=19721,19993 "),
+        "{}",
+        requests[0].0
+    );
+    assert_eq!(requests[0].1, Item::VERSION.to_uppercase());
+
+    // labelled with the endpoint the path belongs to
+    let text = metrics.encode();
+    assert!(text.contains(r#"gw2lib_cache_lookups_total{endpoint="v2/items",result="hit"} 1"#));
+    assert!(text.contains(r#"gw2lib_requests_total{endpoint="v2/items",status="2xx"} 1"#));
+    assert!(!text.contains(r#"endpoint="""#), "{}", text);
+
+    assert!(matches!(
+        client.raw("v2/unknown"),
+        Err(EndpointError::UnknownEndpoint(_))
+    ));
+    assert!(matches!(
+        client.raw("v2/account"),
+        Err(EndpointError::NotAuthenticated)
+    ));
+}
+
+#[test]
+fn registry() {
+    let endpoints = registry::endpoints();
+    let item = endpoints.iter().find(|e| e.url == "v2/items").unwrap();
+    assert!(item.locale);
+    assert!(matches!(
+        item.kind,
+        EndpointKind::Bulk {
+            all: false,
+            id_type: "gw2lib_model::items::ItemId",
+            ..
+        }
+    ));
+
+    let find = |path| registry::find(path).unwrap().type_name;
+    assert_eq!(
+        find("v2/recipes/search?output=19721"),
+        "gw2lib_model::items::recipes::RecipeSearch"
+    );
+    assert_eq!(
+        find("v2/recipes/7314"),
+        "gw2lib_model::items::recipes::Recipe"
+    );
+    assert_eq!(
+        find("v2/characters/My Character/core"),
+        "gw2lib_model::authenticated::characters::Core"
+    );
+    assert_eq!(
+        find("v2/continents/1/floors/1"),
+        "gw2lib_model::misc::continents::Floor"
+    );
+
+    // both account models serve the same url
+    assert_eq!(
+        find("v2/account"),
+        "gw2lib_model::authenticated::account::Account"
+    );
+    let find_versioned = |version| {
+        registry::find_versioned("v2/account", version)
+            .unwrap()
+            .type_name
+    };
+    assert_eq!(
+        find_versioned("2024-07-20T01:00:00.000Z"),
+        "gw2lib_model::authenticated::account::v2024_07_20::Account"
+    );
+    assert_eq!(
+        find_versioned("2025-01-01T00:00:00.000Z"),
+        "gw2lib_model::authenticated::account::v2024_07_20::Account"
+    );
+    assert_eq!(
+        find_versioned("2024-07-19T00:00:00.000Z"),
+        "gw2lib_model::authenticated::account::Account"
+    );
+    assert_eq!(
+        find_versioned("2000-01-01T00:00:00.000Z"),
+        "gw2lib_model::authenticated::account::Account"
+    );
+}
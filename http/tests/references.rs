@@ -0,0 +1,59 @@
+#![cfg(feature = "blocking")]
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use gw2lib::{
+    cache::InMemoryCache,
+    model::{
+        authenticated::account::wallet::Wallet,
+        misc::{currencies::CurrencyId, worlds::WorldId},
+    },
+    Client, Requester,
+};
+
+pub mod setup;
+
+/// answers requests for currencies and worlds and records the request lines
+fn api(requests: Arc<Mutex<Vec<String>>>) -> u16 {
+    setup::mock_api(move |request| {
+        let body = if request.path().starts_with("/v2/currencies") {
+            r#"[{"id":1,"name":"Coin","description":"","icon":"","order":101},{"id":2,"name":"Karma","description":"","icon":"","order":102}]"#
+        } else {
+            r#"[{"id":1001,"name":"Anvil Rock","population":"High"}]"#
+        };
+        requests.lock().unwrap().push(request.line.clone());
+        (200, body.to_string())
+    })
+}
+
+#[test]
+fn resolve() {
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let api = api(requests.clone());
+    let client = Client::empty()
+        .cache(InMemoryCache::default())
+        .host_http(format!("http://127.0.0.1:{}", api));
+
+    let wallet = Wallet(HashMap::from([(CurrencyId(1), 100), (CurrencyId(2), 5)]));
+    let resolved = client.resolve(&wallet).unwrap();
+    assert_eq!(resolved.currencies.len(), 2);
+    assert_eq!(resolved.currencies[&CurrencyId(2)].name, "Karma");
+    assert!(resolved.items.is_empty());
+    assert_eq!(requests.lock().unwrap().len(), 1);
+
+    // everything is cached now
+    let resolved = client.resolve(&wallet).unwrap();
+    assert_eq!(resolved.currencies.len(), 2);
+    assert_eq!(requests.lock().unwrap().len(), 1);
+
+    // duplicate ids are only requested once
+    let worlds = [WorldId(1001), WorldId(1001)];
+    let resolved = client.resolve(&worlds[..]).unwrap();
+    assert_eq!(resolved.worlds.len(), 1);
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests.len(), 2);
+    assert!(requests[1].starts_with("GET /v2/worlds"), "{}", requests[1]);
+}
@@ -1,6 +1,11 @@
 #![cfg(feature = "blocking")]
 
-use std::net::TcpStream;
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream},
+    sync::Arc,
+    thread,
+};
 
 use gw2lib::{self, cache::InMemoryCache, rate_limit::NoopRateLimiter, Client};
 use hyper::client::HttpConnector;
@@ -15,3 +20,86 @@ pub fn setup() -> Client<InMemoryCache, NoopRateLimiter, HttpConnector, true> {
         .api_key(API_KEY)
         .host_http("http://".to_string() + HOST)
 }
+
+/// a request received by [`mock_api`]
+pub struct MockRequest {
+    /// e.g. `GET /v2/worlds?lang=en&ids=1001 HTTP/1.1`
+    pub line: String,
+    /// names are lowercase
+    pub headers: Vec<(String, String)>,
+}
+
+impl MockRequest {
+    /// e.g. `/v2/worlds?lang=en&ids=1001`
+    pub fn path(&self) -> &str {
+        self.line.split(' ').nth(1).unwrap_or_default()
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// serves a fake api on a local port and returns the port
+///
+/// `handler` returns the status and body of every response
+pub fn mock_api<H>(handler: H) -> u16
+where
+    H: Fn(&MockRequest) -> (u16, String) + Send + Sync + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let handler = Arc::new(handler);
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let handler = handler.clone();
+            thread::spawn(move || {
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                // connections are kept alive until the client closes them
+                loop {
+                    let mut line = String::new();
+                    if reader.read_line(&mut line).unwrap_or(0) == 0 {
+                        break;
+                    }
+                    let mut request = MockRequest {
+                        line: line.trim_end().to_string(),
+                        headers: Vec::new(),
+                    };
+                    loop {
+                        line.clear();
+                        if reader.read_line(&mut line).unwrap_or(0) <= 2 {
+                            break;
+                        }
+                        if let Some((name, value)) = line.split_once(':') {
+                            request
+                                .headers
+                                .push((name.to_lowercase(), value.trim().to_string()));
+                        }
+                    }
+
+                    let (status, body) = handler(&request);
+                    let reason = match status {
+                        200 => "OK",
+                        429 => "Too Many Requests",
+                        _ => "Error",
+                    };
+                    let response = format!(
+                        "HTTP/1.1 {} {}\r\ncontent-length: {}\r\n\r\n{}",
+                        status,
+                        reason,
+                        body.len(),
+                        body
+                    );
+                    if stream.write_all(response.as_bytes()).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+    });
+    port
+}
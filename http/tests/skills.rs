@@ -0,0 +1,101 @@
+#![cfg(feature = "blocking")]
+
+use gw2lib::{
+    model::game_mechanics::skills::{Fact, Skill, SkillId, Slot},
+    Requester,
+};
+
+pub mod setup;
+
+#[test]
+fn single() {
+    let client = setup::setup();
+    let skill: Skill = client.single(SkillId(5491)).unwrap();
+    assert_eq!(skill.slot, Some(Slot::Weapon_1));
+}
+
+#[test]
+#[ignore]
+fn all() {
+    let client = setup::setup();
+    let _: Vec<Skill> = client.all().unwrap();
+}
+
+#[test]
+fn facts() {
+    let json = r#"{
+        "id": 5491,
+        "name": "Fireball",
+        "chat_link": "[&BnMVAAA=]",
+        "type": "Weapon",
+        "weapon_type": "Staff",
+        "professions": ["Elementalist"],
+        "slot": "Weapon_1",
+        "attunement": "Fire",
+        "next_chain": 5492,
+        "facts": [
+            {"text": "Damage", "type": "Damage", "hit_count": 1, "dmg_multiplier": 0.8},
+            {"text": "Radius", "type": "Radius", "distance": 180},
+            {"text": "Combo Finisher", "type": "ComboFinisher", "finisher_type": "Projectile", "percent": 20},
+            {"text": "Shiny", "type": "SomethingNew", "value": 3}
+        ],
+        "traited_facts": [
+            {"text": "Burning", "type": "Buff", "status": "Burning", "duration": 2, "apply_count": 1, "requires_trait": 296, "overrides": 1}
+        ]
+    }"#;
+    let skill: Skill = serde_json::from_str(json).unwrap();
+    assert_eq!(skill.next_chain, Some(SkillId(5492)));
+    assert!(matches!(skill.facts[0], Fact::Damage { hit_count: 1, .. }));
+    assert!(matches!(skill.facts[1], Fact::Radius { distance: 180, .. }));
+    let Fact::Unknown(unknown) = &skill.facts[3] else {
+        panic!("{:?}", skill.facts[3]);
+    };
+    assert_eq!(unknown["type"], "SomethingNew");
+    assert!(matches!(
+        skill.traited_facts[0].fact,
+        Fact::Buff {
+            apply_count: Some(1),
+            ..
+        }
+    ));
+
+    // unknown facts survive a round trip
+    let skill: Skill = serde_json::from_value(serde_json::to_value(&skill).unwrap()).unwrap();
+    assert!(matches!(skill.facts[3], Fact::Unknown(_)));
+
+    // errors in known fact types still surface
+    let broken = r#"{"type": "Damage", "hit_count": "many"}"#;
+    assert!(serde_json::from_str::<Fact>(broken).is_err());
+}
+
+#[test]
+#[cfg(not(feature = "strict"))]
+fn unknown_fact_fields() {
+    let json = r#"{"text": "Radius", "type": "Radius", "distance": 180, "shiny": true}"#;
+    let fact: Fact = serde_json::from_str(json).unwrap();
+    let Fact::Unknown(unknown) = &fact else {
+        panic!("{:?}", fact);
+    };
+    assert_eq!(unknown["shiny"], true);
+    assert_eq!(
+        serde_json::to_value(&fact).unwrap(),
+        serde_json::from_str::<serde_json::Value>(json).unwrap()
+    );
+
+    let json = r#"{"text": "Radius", "type": "Radius", "distance": 180}"#;
+    let fact: Fact = serde_json::from_str(json).unwrap();
+    assert!(matches!(fact, Fact::Radius { distance: 180, .. }));
+}
+
+#[test]
+#[cfg(feature = "strict")]
+fn strict_traited_facts() {
+    use gw2lib::model::game_mechanics::skills::TraitedFact;
+
+    let json = r#"{"type": "Buff", "requires_trait": 296}"#;
+    let fact: TraitedFact = serde_json::from_str(json).unwrap();
+    assert!(matches!(fact.fact, Fact::Buff { .. }));
+
+    let json = r#"{"type": "Buff", "requires_trait": 296, "shiny": true}"#;
+    assert!(serde_json::from_str::<TraitedFact>(json).is_err());
+}
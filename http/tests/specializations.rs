@@ -0,0 +1,43 @@
+#![cfg(feature = "blocking")]
+
+use gw2lib::{
+    model::{
+        authenticated::characters::Profession,
+        game_mechanics::{
+            specializations::{Specialization, SpecializationId},
+            traits::TraitId,
+        },
+    },
+    Requester,
+};
+
+pub mod setup;
+
+#[test]
+fn all() {
+    let client = setup::setup();
+    let _: Vec<Specialization> = client.all().unwrap();
+}
+
+#[test]
+fn parse() {
+    let json = r#"{
+        "id": 48,
+        "name": "Tempest",
+        "profession": "Elementalist",
+        "elite": true,
+        "minor_traits": [2064, 2045, 2039],
+        "major_traits": [1952, 1962, 2015, 1886, 2003, 1967, 2031, 2033, 2017],
+        "weapon_trait": 2043,
+        "icon": "https://render.guildwars2.com/file/icon.png",
+        "background": "https://render.guildwars2.com/file/background.png",
+        "profession_icon_big": "https://render.guildwars2.com/file/big.png",
+        "profession_icon": "https://render.guildwars2.com/file/small.png"
+    }"#;
+    let spec: Specialization = serde_json::from_str(json).unwrap();
+    assert_eq!(spec.id, SpecializationId(48));
+    assert_eq!(spec.profession, Profession::Elementalist);
+    assert!(spec.elite);
+    assert_eq!(spec.major_traits.len(), 9);
+    assert_eq!(spec.weapon_trait, Some(TraitId(2043)));
+}
@@ -0,0 +1,140 @@
+#![cfg(all(feature = "blocking", feature = "tracing"))]
+
+use std::{
+    collections::BTreeMap,
+    fmt::Debug,
+    sync::{Arc, Mutex},
+};
+
+use gw2lib::{
+    cache::InMemoryCache,
+    model::{
+        misc::worlds::{World, WorldId},
+        Endpoint,
+    },
+    Client, Requester,
+};
+use tracing::{
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+    Event, Metadata, Subscriber,
+};
+
+pub mod setup;
+
+type Fields = BTreeMap<String, String>;
+
+/// keeps the fields of every span and event
+#[derive(Clone, Default)]
+struct Capture(Arc<Mutex<Captured>>);
+
+#[derive(Default)]
+struct Captured {
+    spans: Vec<(&'static str, Fields)>,
+    events: Vec<Fields>,
+}
+
+struct Visitor<'a>(&'a mut Fields);
+
+impl Visit for Visitor<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{:?}", value));
+    }
+}
+
+impl Subscriber for Capture {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut fields = Fields::new();
+        span.record(&mut Visitor(&mut fields));
+        let mut captured = self.0.lock().unwrap();
+        captured.spans.push((span.metadata().name(), fields));
+        Id::from_u64(captured.spans.len() as u64)
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Fields::new();
+        event.record(&mut Visitor(&mut fields));
+        self.0.lock().unwrap().events.push(fields);
+    }
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+impl Captured {
+    fn events(&self, message: &str) -> Vec<&Fields> {
+        self.events
+            .iter()
+            .filter(|e| e.get("message").map(String::as_str) == Some(message))
+            .collect()
+    }
+}
+
+/// answers with a world, 1002 has an invalid population
+fn api() -> u16 {
+    setup::mock_api(|request| {
+        let body = if request.path().contains("1002") {
+            r#"{"id":1002,"name":"Borlis Pass","population":5}"#
+        } else {
+            r#"{"id":1001,"name":"Anvil Rock","population":"High"}"#
+        };
+        (200, body.to_string())
+    })
+}
+
+#[test]
+fn request_span() {
+    let api = api();
+    let client = Client::empty()
+        .cache(InMemoryCache::default())
+        .host_http(format!("http://127.0.0.1:{}", api))
+        .api_key("secret-key");
+
+    let capture = Capture::default();
+    tracing::subscriber::with_default(capture.clone(), || {
+        let _: World = client.single(WorldId(1001)).unwrap();
+        let _: World = client.single(WorldId(1001)).unwrap();
+        assert!(client.single::<World, _>(WorldId(1002)).is_err());
+    });
+
+    let captured = capture.0.lock().unwrap();
+    let requests: Vec<_> = captured
+        .spans
+        .iter()
+        .filter(|(name, _)| *name == "request")
+        .map(|(_, fields)| fields)
+        .collect();
+    assert_eq!(requests.len(), 2);
+    let span = requests[0];
+    assert_eq!(span["endpoint"], "v2/worlds");
+    assert_eq!(span["version"], World::VERSION);
+    assert!(span["url"].contains("/v2/worlds/1001"), "{}", span["url"]);
+    assert!(!span["url"].contains("secret-key"), "{}", span["url"]);
+
+    let lookups = captured.events("cache lookup");
+    let hits: Vec<_> = lookups.iter().map(|e| e["hit"].as_str()).collect();
+    assert_eq!(hits, ["false", "true", "false"]);
+    assert!(lookups.iter().all(|e| e["endpoint"] == "v2/worlds"));
+
+    let failures = captured.events("failed to deserialize response");
+    assert_eq!(failures.len(), 1);
+    assert!(
+        failures[0]["error"].contains("population"),
+        "{:?}",
+        failures[0]
+    );
+}
@@ -0,0 +1,109 @@
+#![cfg(feature = "blocking")]
+
+use gw2lib::model::{
+    authenticated::Permissions,
+    items::{Details, Item, ItemType, Rarity},
+    misc::worlds::PopulationLevel,
+};
+
+#[test]
+fn unknown_variant() {
+    let population: PopulationLevel = serde_json::from_str(r#""Crowded""#).unwrap();
+    assert_eq!(population, PopulationLevel::Unknown("Crowded".to_string()));
+    assert_eq!(serde_json::to_string(&population).unwrap(), r#""Crowded""#);
+
+    let permissions: Vec<Permissions> =
+        serde_json::from_str(r#"["account","wizardsvault"]"#).unwrap();
+    assert_eq!(
+        permissions,
+        vec![
+            Permissions::Account,
+            Permissions::Unknown("wizardsvault".to_string())
+        ]
+    );
+    assert_eq!(
+        serde_json::to_string(&permissions).unwrap(),
+        r#"["account","wizardsvault"]"#
+    );
+}
+
+#[test]
+fn unknown_details() {
+    let json = r#"{"id":1,"chat_link":"[&AgEBAAAA]","name":"Relic","icon":null,"description":null,"rarity":"Celestial","level":0,"vendor_value":0,"default_skin":null,"flags":[],"game_types":[],"restrictions":[],"type":"Relic","details":{"charges":3}}"#;
+    let item: Item = serde_json::from_str(json).unwrap();
+    assert_eq!(item.rarity, Rarity::Unknown("Celestial".to_string()));
+    let Details::Unknown(details) = &item.details else {
+        panic!("unexpected details: {:?}", item.details);
+    };
+    assert_eq!(details.details, Some(serde_json::json!({"charges": 3})));
+
+    let round_trip: Item = serde_json::from_str(&serde_json::to_string(&item).unwrap()).unwrap();
+    assert_eq!(round_trip, item);
+    assert_eq!(
+        ItemType::from(item.details),
+        ItemType::Unknown("Relic".to_string())
+    );
+}
+
+#[test]
+#[cfg(not(feature = "strict"))]
+fn unknown_fields() {
+    let json = r#"{"id":1,"chat_link":"[&AgEBAAAA]","name":"Helm","icon":null,"description":null,"rarity":"Rare","level":80,"vendor_value":0,"default_skin":null,"flags":[],"game_types":[],"restrictions":[],"upgrades_into":[2],"type":"Armor","details":{"type":"Helm","weight_class":"Heavy","defense":97,"attribute_adjustment":1.0,"infusion_slots":[],"infix_upgrade":null,"suffix_item_id":null,"secondary_suffix_item_id":null,"stat_choices":null,"glow":true}}"#;
+    let item: Item = serde_json::from_str(json).unwrap();
+    assert_eq!(item.extra.len(), 1);
+    assert_eq!(item.extra["upgrades_into"], serde_json::json!([2]));
+    let Details::Armor(armor) = &item.details else {
+        panic!("unexpected details: {:?}", item.details);
+    };
+    assert_eq!(armor.extra.len(), 1);
+    assert_eq!(armor.extra["glow"], serde_json::json!(true));
+
+    let round_trip: serde_json::Value = serde_json::to_value(&item).unwrap();
+    assert_eq!(
+        round_trip,
+        serde_json::from_str::<serde_json::Value>(json).unwrap()
+    );
+}
+
+#[test]
+fn ordering_ignores_extra() {
+    use std::cmp::Ordering;
+
+    let json = r#"{"id":1,"chat_link":"[&AgEBAAAA]","name":"Helm","icon":null,"description":null,"rarity":"Rare","level":80,"vendor_value":0,"default_skin":null,"flags":[],"game_types":[],"restrictions":[],"type":"Armor","details":{"type":"Helm","weight_class":"Heavy","defense":97,"attribute_adjustment":1.0,"infusion_slots":[],"infix_upgrade":null,"suffix_item_id":null,"secondary_suffix_item_id":null,"stat_choices":null}}"#;
+    let item: Item = serde_json::from_str(json).unwrap();
+    let mut higher = item.clone();
+    higher.level = 81;
+    assert_eq!(item.partial_cmp(&item), Some(Ordering::Equal));
+    assert_eq!(item.partial_cmp(&higher), Some(Ordering::Less));
+
+    // only differing in extra, but still not equal
+    let mut unknown = item.clone();
+    unknown
+        .extra
+        .insert("upgrades_into".to_string(), serde_json::json!([2]));
+    assert_eq!(item.partial_cmp(&unknown), None);
+    higher.extra = unknown.extra.clone();
+    assert_eq!(item.partial_cmp(&higher), Some(Ordering::Less));
+}
+
+#[test]
+#[cfg(feature = "strict")]
+fn strict_unknown_fields() {
+    let json = r#"{"id":1,"chat_link":"[&AgEBAAAA]","name":"Helm","icon":null,"description":null,"rarity":"Rare","level":80,"vendor_value":0,"default_skin":null,"flags":[],"game_types":[],"restrictions":[],"type":"Armor","details":{"type":"Helm","weight_class":"Heavy","defense":97,"attribute_adjustment":1.0,"infusion_slots":[],"infix_upgrade":null,"suffix_item_id":null,"secondary_suffix_item_id":null,"stat_choices":null}}"#;
+    let item: Item = serde_json::from_str(json).unwrap();
+    assert!(item.extra.is_empty());
+
+    let unknown = json.replace(
+        r#""restrictions":[]"#,
+        r#""restrictions":[],"upgrades_into":[2]"#,
+    );
+    let err = serde_json::from_str::<Item>(&unknown).unwrap_err();
+    assert!(err.to_string().contains("upgrades_into"), "{err}");
+
+    let unknown = json.replace(
+        r#""stat_choices":null"#,
+        r#""stat_choices":null,"glow":true"#,
+    );
+    let err = serde_json::from_str::<Item>(&unknown).unwrap_err();
+    assert!(err.to_string().contains("glow"), "{err}");
+}
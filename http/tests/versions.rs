@@ -0,0 +1,94 @@
+#![cfg(feature = "blocking")]
+
+use std::sync::{Arc, Mutex};
+
+use gw2lib::{
+    cache::InMemoryCache,
+    model::{
+        authenticated::account::v2024_07_20::{Account, WvwTeamId},
+        misc::{
+            build::Build,
+            worlds::{World, WorldId},
+        },
+        Endpoint,
+    },
+    Client, EndpointError, Requester,
+};
+
+pub mod setup;
+
+/// answers every request with a build and records the schema versions
+fn api(versions: Arc<Mutex<Vec<String>>>) -> u16 {
+    setup::mock_api(move |request| {
+        if let Some(version) = request.header("x-schema-version") {
+            versions.lock().unwrap().push(version.to_uppercase());
+        }
+        (200, r#"{"id":115267}"#.to_string())
+    })
+}
+
+#[test]
+fn version_override() {
+    let versions = Arc::new(Mutex::new(Vec::new()));
+    let api = api(versions.clone());
+    let client = Client::empty()
+        .cache(InMemoryCache::default())
+        .host_http(format!("http://127.0.0.1:{}", api));
+
+    let legacy = "2019-12-19T00:00:00.000Z";
+    let _: Build = client.get().unwrap();
+    let _: Build = client.version(legacy).get().unwrap();
+    // both are cached on their own
+    let _: Build = client.get().unwrap();
+    let _: Build = client.version(legacy).get().unwrap();
+
+    assert_eq!(
+        *versions.lock().unwrap(),
+        vec![Build::VERSION.to_uppercase(), legacy.to_uppercase()]
+    );
+}
+
+#[test]
+fn version_in_errors() {
+    let versions = Arc::new(Mutex::new(Vec::new()));
+    let api = api(versions.clone());
+    let client = Client::empty().host_http(format!("http://127.0.0.1:{}", api));
+
+    let legacy = "2019-12-19T00:00:00.000Z";
+    let version = |err| match err {
+        EndpointError::InvalidJsonResponse(err) => err.version,
+        err => panic!("unexpected error: {}", err),
+    };
+    let err = client.single::<World, _>(WorldId(1001)).unwrap_err();
+    assert_eq!(version(err), World::VERSION);
+    let err = client
+        .version(legacy)
+        .single::<World, _>(WorldId(1001))
+        .unwrap_err();
+    assert_eq!(version(err), legacy);
+    let err = client
+        .version(legacy)
+        .many_lenient::<World, _>(vec![WorldId(1001)])
+        .unwrap_err();
+    assert_eq!(version(err), legacy);
+}
+
+#[test]
+fn versioned_model() {
+    let json = r#"{
+        "id": "00000000-0000-0000-0000-000000000000",
+        "age": 3600,
+        "name": "Account.1234",
+        "world": 1001,
+        "guilds": [],
+        "created": "2012-08-28T00:00:00Z",
+        "access": ["GuildWars2"],
+        "commander": false,
+        "wvw": {"team_id": 11005, "rank": 42},
+        "last_modified": "2024-07-21T00:00:00Z"
+    }"#;
+    let account: Account = serde_json::from_str(json).unwrap();
+    assert_eq!(account.wvw.team_id, WvwTeamId(11005));
+    assert_eq!(account.wvw.rank, Some(42));
+    assert_eq!(Account::VERSION, "2024-07-20T01:00:00.000Z");
+}
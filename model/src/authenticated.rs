@@ -6,6 +6,7 @@ pub mod characters;
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
+#[non_exhaustive]
 pub enum Permissions {
     Account,
     Builds,
@@ -17,12 +18,17 @@ pub enum Permissions {
     TradingPost,
     Unlocks,
     Wallet,
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum TokenType {
     APIKey,
     Subtoken,
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -41,6 +47,8 @@ pub struct Tokeninfo {
     pub _type: TokenType,
     #[serde(flatten)]
     details: Option<SubtokenDetails>,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
 }
 
 impl Endpoint for Tokeninfo {
@@ -1,11 +1,15 @@
 pub mod bank;
 pub mod materials;
+pub mod v2024_07_20;
 pub mod wallet;
 
 use serde::{Deserialize, Serialize};
 
 pub use crate::misc::worlds::WorldId;
-use crate::*;
+use crate::{
+    references::{ReferenceSet, References},
+    *,
+};
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 #[non_exhaustive]
@@ -16,6 +20,8 @@ pub enum Access {
     HeartOfThorns,
     PathOfFire,
     EndOfDragons,
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -33,12 +39,21 @@ pub struct Account {
     pub daily_ap: Option<u16>,
     pub monthly_ap: Option<u16>,
     pub wvw_rank: Option<u16>,
-    pub last_modified: String,
+    pub last_modified: TimeStamp,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
+}
+
+impl References for Account {
+    fn references(&self, set: &mut ReferenceSet) {
+        self.world.references(set);
+    }
 }
 
 impl Endpoint for Account {
     const AUTHENTICATED: bool = true;
     const LOCALE: bool = false;
+    const PERMISSIONS: &'static [Permissions] = &[Permissions::Account];
     const URL: &'static str = "v2/account";
     const VERSION: &'static str = "2022-07-22T00:00:00.000Z";
 }
@@ -1,19 +1,39 @@
 use serde::{Deserialize, Serialize};
 
-use crate::{items::ItemId, Endpoint, FixedEndpoint};
+use crate::{
+    authenticated::Permissions,
+    items::ItemId,
+    references::{ReferenceSet, References},
+    Endpoint, FixedEndpoint,
+};
 
 pub type AccountMaterials = Vec<AccountMaterial>;
 
-#[derive(Clone, PartialEq, Eq, PartialOrd, Debug, Serialize, Deserialize)]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub struct AccountMaterial {
     pub id: ItemId,
     pub category: u32,
     pub count: u32,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
+}
+
+partial_ord_without_extra!(AccountMaterial {
+    id,
+    category,
+    count
+});
+
+impl References for AccountMaterial {
+    fn references(&self, set: &mut ReferenceSet) {
+        self.id.references(set);
+    }
 }
 
 impl Endpoint for AccountMaterials {
     const AUTHENTICATED: bool = true;
     const LOCALE: bool = false;
+    const PERMISSIONS: &'static [Permissions] = &[Permissions::Account, Permissions::Inventories];
     const URL: &'static str = "v2/account/materials";
     const VERSION: &'static str = "2022-07-25T00:00:00.000Z";
 }
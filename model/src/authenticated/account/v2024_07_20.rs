@@ -0,0 +1,57 @@
+//! [`Account`](super::Account) since the wvw rank moved into `wvw`, next to
+//! the team of the account
+
+use serde::{Deserialize, Serialize};
+
+use super::{Access, WorldId};
+use crate::{
+    references::{ReferenceSet, References},
+    *,
+};
+
+id!(WvwTeamId(u32));
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Wvw {
+    pub team_id: WvwTeamId,
+    /// requires the progression permission
+    pub rank: Option<u16>,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Account {
+    pub id: String,
+    pub age: u64,
+    pub name: String,
+    pub world: WorldId,
+    pub guilds: Vec<String>,
+    pub guild_leader: Option<Vec<String>>,
+    pub created: TimeStamp,
+    pub access: Vec<Access>,
+    pub commander: bool,
+    pub fractal_level: Option<u8>,
+    pub daily_ap: Option<u16>,
+    pub monthly_ap: Option<u16>,
+    pub wvw: Wvw,
+    pub last_modified: TimeStamp,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
+}
+
+impl References for Account {
+    fn references(&self, set: &mut ReferenceSet) {
+        self.world.references(set);
+    }
+}
+
+impl Endpoint for Account {
+    const AUTHENTICATED: bool = true;
+    const LOCALE: bool = false;
+    const PERMISSIONS: &'static [Permissions] = &[Permissions::Account];
+    const URL: &'static str = "v2/account";
+    const VERSION: &'static str = "2024-07-20T01:00:00.000Z";
+}
+
+impl FixedEndpoint for Account {}
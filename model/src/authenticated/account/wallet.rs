@@ -5,7 +5,12 @@ use std::{
 
 use serde::{Deserialize, Serialize};
 
-use crate::{misc::currencies::CurrencyId, Endpoint, FixedEndpoint};
+use crate::{
+    authenticated::Permissions,
+    misc::currencies::CurrencyId,
+    references::{ReferenceSet, References},
+    Endpoint, FixedEndpoint,
+};
 
 type InnerWallet = HashMap<CurrencyId, u32>;
 
@@ -16,12 +21,21 @@ pub struct Wallet(#[serde(with = "internal_wallet")] pub InnerWallet);
 impl Endpoint for Wallet {
     const AUTHENTICATED: bool = true;
     const LOCALE: bool = false;
+    const PERMISSIONS: &'static [Permissions] = &[Permissions::Account, Permissions::Wallet];
     const URL: &'static str = "v2/account/wallet";
     const VERSION: &'static str = "2022-07-25T00:00:00.000Z";
 }
 
 impl FixedEndpoint for Wallet {}
 
+impl References for Wallet {
+    fn references(&self, set: &mut ReferenceSet) {
+        for currency in self.keys() {
+            currency.references(set);
+        }
+    }
+}
+
 impl Deref for Wallet {
     type Target = InnerWallet;
 
@@ -7,36 +7,53 @@ use crate::{
     items::{itemstats::StatsId, recipes::RecipeId, skins::SkinId, AttributeType, ItemId},
     misc::{colors::ColorId, titles::TitleId},
     pvp::amulets::AmuletId,
+    references::{ReferenceSet, References},
     wvw::abilities::AbilityId,
-    BulkEndpoint, Endpoint, EndpointWithId, TimeStamp,
+    Endpoint, TimeStamp,
 };
 
 pub type Age = u64;
 pub type CharacterId = String;
 pub type BackStoryId = String;
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[derive(Clone, Debug, Serialize, Deserialize, Endpoint)]
+#[endpoint(
+    url = "v2/characters",
+    version = "2022-06-14T00:00:00.000Z",
+    authenticated,
+    permissions(Account, Characters),
+    id_type = "CharacterId",
+    suffix = "backstory"
+)]
 pub struct Backstory {
     pub backstory: Vec<BackStoryId>,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum Race {
     Asura,
     Charr,
     Human,
     Norn,
     Sylvari,
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum Gender {
     Male,
     Female,
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum Profession {
     Elementalist,
     Engineer,
@@ -47,10 +64,19 @@ pub enum Profession {
     Revenant,
     Thief,
     Warrior,
-}
-
-#[derive(Clone, Debug, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+    #[serde(untagged)]
+    Unknown(String),
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Endpoint)]
+#[endpoint(
+    url = "v2/characters",
+    version = "2022-06-14T00:00:00.000Z",
+    authenticated,
+    permissions(Account, Characters),
+    id_type = "CharacterId",
+    suffix = "core"
+)]
 pub struct Core {
     pub name: CharacterId,
     pub race: Race,
@@ -63,9 +89,12 @@ pub struct Core {
     pub last_modified: TimeStamp,
     pub deaths: u32,
     pub title: Option<TitleId>,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum Discipline {
     Armorsmith,
     Artificer,
@@ -76,23 +105,36 @@ pub enum Discipline {
     Scribe,
     Tailor,
     Weaponsmith,
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
 pub struct Craft {
     pub discipline: Discipline,
     pub rating: u16,
     pub active: bool,
-}
-
-#[derive(Clone, Debug, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+    #[serde(flatten)]
+    pub extra: crate::Extra,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Endpoint)]
+#[endpoint(
+    url = "v2/characters",
+    version = "2022-06-14T00:00:00.000Z",
+    authenticated,
+    permissions(Account, Characters),
+    id_type = "CharacterId",
+    suffix = "crafting"
+)]
 pub struct Crafting {
     pub crafting: Vec<Craft>,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum Slot {
     HelmAquatic,
     Backpack,
@@ -116,30 +158,39 @@ pub enum Slot {
     Sickle,
     Axe,
     Pick,
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 #[derive(Clone, PartialEq, Debug, Serialize, Deserialize, Eq)]
 pub struct Stats {
     pub id: StatsId,
     pub attributes: HashMap<AttributeType, u16>,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
 }
 
 #[derive(Clone, PartialEq, Debug, Serialize, Deserialize, Eq)]
+#[non_exhaustive]
 pub enum Binding {
     Character,
     Account,
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum Location {
     Equipped,
     Armory,
     EquippedFromLegendaryArmory,
     LegendaryArmory,
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 #[derive(Clone, PartialEq, Debug, Serialize, Deserialize, Eq)]
-#[serde(deny_unknown_fields)]
 pub struct Equip {
     pub id: ItemId,
     pub slot: Option<Slot>,
@@ -156,12 +207,38 @@ pub struct Equip {
     pub dyes: Option<Vec<Option<ColorId>>>,
     /// only present in character.equipment
     pub tabs: Option<Vec<usize>>,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+impl References for Equip {
+    fn references(&self, set: &mut ReferenceSet) {
+        self.id.references(set);
+        self.infusions.references(set);
+        self.upgrades.references(set);
+        self.dyes.references(set);
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Endpoint)]
+#[endpoint(
+    url = "v2/characters",
+    version = "2022-06-14T00:00:00.000Z",
+    authenticated,
+    permissions(Account, Builds, Characters),
+    id_type = "CharacterId",
+    suffix = "equipment"
+)]
 pub struct Equipment {
     pub equipment: Vec<Equip>,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
+}
+
+impl References for Equipment {
+    fn references(&self, set: &mut ReferenceSet) {
+        self.equipment.references(set);
+    }
 }
 
 #[derive(Clone, PartialEq, Debug, Serialize, Deserialize, Eq)]
@@ -177,91 +254,148 @@ pub struct InventoryItem {
     pub binding: Option<Binding>,
     pub bound_to: Option<String>,
     pub dyes: Option<Vec<ColorId>>,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
+}
+
+impl References for InventoryItem {
+    fn references(&self, set: &mut ReferenceSet) {
+        self.id.references(set);
+        self.infusions.references(set);
+        self.upgrades.references(set);
+        self.dyes.references(set);
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
 pub struct InventoryBag {
     pub id: ItemId,
     pub size: u8,
     pub inventory: Vec<Option<InventoryItem>>,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+impl References for InventoryBag {
+    fn references(&self, set: &mut ReferenceSet) {
+        self.id.references(set);
+        self.inventory.references(set);
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Endpoint)]
+#[endpoint(
+    url = "v2/characters",
+    version = "2022-06-14T00:00:00.000Z",
+    authenticated,
+    permissions(Account, Characters, Inventories),
+    id_type = "CharacterId",
+    suffix = "inventory"
+)]
 pub struct Inventory {
     pub bags: Vec<Option<InventoryBag>>,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
+}
+
+impl References for Inventory {
+    fn references(&self, set: &mut ReferenceSet) {
+        self.bags.references(set);
+    }
 }
 
 pub type Utilities = [Option<SkillId>; 3];
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
 pub struct Skillset {
     pub heal: Option<SkillId>,
     pub utilities: Utilities,
     pub elite: Option<SkillId>,
     // TODO: legends enum
     pub legends: Option<Vec<String>>,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
 pub struct SkillDataSet {
     pub pve: Skillset,
     pub pvp: Skillset,
     pub wvw: Skillset,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
 }
 
 pub type TraitSet = [Option<TraitId>; 3];
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
 pub struct TraitLine {
     pub id: Option<SpecializationId>,
     pub traits: Option<TraitSet>,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
 }
 
 pub type Specialization = [Option<TraitLine>; 3];
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
 pub struct SpecializationSet {
     pub pve: Specialization,
     pub pvp: Specialization,
     pub wvw: Specialization,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
 pub struct TrainingSet {
     // TODO: training id
     pub id: u64,
     pub spent: u16,
     pub done: bool,
-}
-
-#[derive(Clone, Debug, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+    #[serde(flatten)]
+    pub extra: crate::Extra,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Endpoint)]
+#[endpoint(
+    url = "v2/characters",
+    version = "2022-06-14T00:00:00.000Z",
+    authenticated,
+    permissions(Account, Builds, Characters),
+    id_type = "CharacterId",
+    suffix = "training"
+)]
 pub struct Training {
     pub training: Vec<TrainingSet>,
-}
-
-#[derive(Clone, Debug, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+    #[serde(flatten)]
+    pub extra: crate::Extra,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Endpoint)]
+#[endpoint(
+    url = "v2/characters",
+    version = "2022-06-14T00:00:00.000Z",
+    authenticated,
+    permissions(Account, Characters),
+    id_type = "CharacterId",
+    suffix = "recipes"
+)]
 pub struct Recipes {
     pub recipes: Vec<RecipeId>,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
 pub struct WvwAbility {
     pub id: AbilityId,
     pub rank: u8,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
 pub struct EquipmentPvp {
     pub amulet: Option<AmuletId>,
     pub rune: Option<ItemId>,
@@ -271,34 +405,40 @@ pub struct EquipmentPvp {
         Option<ItemId>,
         Option<ItemId>,
     ),
+    #[serde(flatten)]
+    pub extra: crate::Extra,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum Flags {
     Beta,
+    #[serde(untagged)]
+    Unknown(String),
 }
 
-pub type PetId = u16;
+id!(PetId(u16));
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
-#[serde(deny_unknown_fields)]
 pub struct BuildPets {
     pub terrestrial: [Option<PetId>; 2],
     pub aquatic: [Option<PetId>; 2],
+    #[serde(flatten)]
+    pub extra: crate::Extra,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
 pub struct BuildLegends {
     pub legends: LegendSlots,
     pub aquatic_legends: LegendSlots,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
 }
 
 pub type LegendId = String;
 pub type LegendSlots = [Option<LegendId>; 2];
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
 pub struct BuildTemplate {
     pub name: Option<String>,
     pub profession: Option<Profession>,
@@ -308,19 +448,21 @@ pub struct BuildTemplate {
     pub pets: Option<BuildPets>,
     pub legends: Option<LegendSlots>,
     pub aquatic_legends: Option<LegendSlots>,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
 pub struct BuildTab {
     /// this index starts at 1
     pub tab: usize,
     pub is_active: bool,
     pub build: BuildTemplate,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
 pub struct EquipmentTab {
     /// this index starts at 1
     pub tab: usize,
@@ -328,12 +470,29 @@ pub struct EquipmentTab {
     pub is_active: bool,
     pub equipment: Vec<Equip>,
     pub equipment_pvp: Option<EquipmentPvp>,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+impl References for EquipmentTab {
+    fn references(&self, set: &mut ReferenceSet) {
+        self.equipment.references(set);
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Endpoint)]
+#[endpoint(
+    url = "v2/characters",
+    version = "2022-06-14T00:00:00.000Z",
+    authenticated,
+    permissions(Account, Characters),
+    id_type = "CharacterId",
+    id = "core.name",
+    bulk
+)]
 pub struct Character {
     pub backstory: Vec<BackStoryId>,
+    /// unknown fields end up in `core.extra`
     #[serde(flatten)]
     pub core: Core,
     pub crafting: Vec<Craft>,
@@ -358,126 +517,11 @@ pub struct Character {
     pub flags: Vec<Flags>,
 }
 
-impl EndpointWithId for Character {
-    type IdType = CharacterId;
-}
-
-impl Endpoint for Character {
-    const AUTHENTICATED: bool = true;
-    const LOCALE: bool = false;
-    const URL: &'static str = "v2/characters";
-    const VERSION: &'static str = "2022-06-14T00:00:00.000Z";
-}
-
-impl BulkEndpoint for Character {
-    const ALL: bool = true;
-
-    fn id(&self) -> &Self::IdType {
-        &self.core.name
-    }
-}
-
-impl EndpointWithId for Core {
-    type IdType = CharacterId;
-
-    fn format_url(id: &str) -> String {
-        format!("{}/{}/core", Self::URL, id)
-    }
-}
-
-impl Endpoint for Core {
-    const AUTHENTICATED: bool = true;
-    const LOCALE: bool = false;
-    const URL: &'static str = "v2/characters";
-    const VERSION: &'static str = "2022-06-14T00:00:00.000Z";
-}
-
-impl EndpointWithId for Backstory {
-    type IdType = CharacterId;
-
-    fn format_url(id: &str) -> String {
-        format!("{}/{}/backstory", Self::URL, id)
-    }
-}
-
-impl Endpoint for Backstory {
-    const AUTHENTICATED: bool = true;
-    const LOCALE: bool = false;
-    const URL: &'static str = "v2/characters";
-    const VERSION: &'static str = "2022-06-14T00:00:00.000Z";
-}
-
-impl EndpointWithId for Crafting {
-    type IdType = CharacterId;
-
-    fn format_url(id: &str) -> String {
-        format!("{}/{}/crafting", Self::URL, id)
-    }
-}
-
-impl Endpoint for Crafting {
-    const AUTHENTICATED: bool = true;
-    const LOCALE: bool = false;
-    const URL: &'static str = "v2/characters";
-    const VERSION: &'static str = "2022-06-14T00:00:00.000Z";
-}
-
-impl EndpointWithId for Equipment {
-    type IdType = CharacterId;
-
-    fn format_url(id: &str) -> String {
-        format!("{}/{}/equipment", Self::URL, id)
-    }
-}
-
-impl Endpoint for Equipment {
-    const AUTHENTICATED: bool = true;
-    const LOCALE: bool = false;
-    const URL: &'static str = "v2/characters";
-    const VERSION: &'static str = "2022-06-14T00:00:00.000Z";
-}
-
-impl EndpointWithId for Inventory {
-    type IdType = CharacterId;
-
-    fn format_url(id: &str) -> String {
-        format!("{}/{}/inventory", Self::URL, id)
-    }
-}
-
-impl Endpoint for Inventory {
-    const AUTHENTICATED: bool = true;
-    const LOCALE: bool = false;
-    const URL: &'static str = "v2/characters";
-    const VERSION: &'static str = "2022-06-14T00:00:00.000Z";
-}
-
-impl EndpointWithId for Recipes {
-    type IdType = CharacterId;
-
-    fn format_url(id: &str) -> String {
-        format!("{}/{}/recipes", Self::URL, id)
+impl References for Character {
+    fn references(&self, set: &mut ReferenceSet) {
+        self.equipment.references(set);
+        self.bags.references(set);
+        self.recipes.references(set);
+        self.equipment_tabs.references(set);
     }
 }
-
-impl Endpoint for Recipes {
-    const AUTHENTICATED: bool = true;
-    const LOCALE: bool = false;
-    const URL: &'static str = "v2/characters";
-    const VERSION: &'static str = "2022-06-14T00:00:00.000Z";
-}
-
-impl EndpointWithId for Training {
-    type IdType = CharacterId;
-
-    fn format_url(id: &str) -> String {
-        format!("{}/{}/training", Self::URL, id)
-    }
-}
-
-impl Endpoint for Training {
-    const AUTHENTICATED: bool = true;
-    const LOCALE: bool = false;
-    const URL: &'static str = "v2/characters";
-    const VERSION: &'static str = "2022-06-14T00:00:00.000Z";
-}
@@ -1,45 +1,76 @@
-use serde::{Deserialize, Serialize};
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::game_mechanics::traits::TraitId;
 pub use crate::{authenticated::characters::Profession, items::WeaponType};
+use crate::{
+    game_mechanics::{specializations::SpecializationId, traits::TraitId},
+    items::AttributeType,
+    Endpoint,
+};
 
-pub type SkillId = u32;
+id!(SkillId(u32));
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum SkillType {
     Bundle,
     Elite,
     Heal,
+    Monster,
+    Pet,
     Profession,
+    Toolbelt,
+    Transform,
     Utility,
     Weapon,
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 #[allow(non_camel_case_types)]
+#[non_exhaustive]
 pub enum Slot {
     Downed_1,
     Downed_2,
     Downed_3,
     Downed_4,
+    Elite,
+    Heal,
     Pet,
     Profession_1,
     Profession_2,
     Profession_3,
     Profession_4,
     Profession_5,
+    Toolbelt,
+    Transform_1,
     Utility,
     Weapon_1,
     Weapon_2,
     Weapon_3,
     Weapon_4,
     Weapon_5,
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Attunement {
+    Fire,
+    Water,
+    Air,
+    Earth,
+    #[serde(untagged)]
+    Unknown(String),
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum FactsType {
     AttributeAdjust,
     Buff,
+    BuffConversion,
     ComboField,
     ComboFinisher,
     Damage,
@@ -54,20 +85,217 @@ pub enum FactsType {
     Radius,
     Range,
     Recharge,
+    StunBreak,
     Time,
     Unblockable,
+    #[serde(untagged)]
+    Unknown(String),
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
-pub struct Fact {
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ComboFieldType {
+    Air,
+    Dark,
+    Ethereal,
+    Fire,
+    Ice,
+    Light,
+    Lightning,
+    Poison,
+    Smoke,
+    Water,
+    #[serde(untagged)]
+    Unknown(String),
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ComboFinisherType {
+    Blast,
+    Leap,
+    Projectile,
+    Whirl,
+    #[serde(untagged)]
+    Unknown(String),
+}
+
+/// the buff a [`Fact::PrefixedBuff`] shows in front of its own
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FactPrefix {
     pub text: String,
-    pub icon: Option<String>,
-    pub _type: FactsType,
+    pub icon: String,
+    pub status: Option<String>,
+    pub description: Option<String>,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
+}
+
+/// durations are in seconds
+///
+/// facts of a known type with fields this library doesn't model are kept as
+/// [`Fact::Unknown`], the `strict` feature rejects them instead
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(remote = "Self", tag = "type")]
+#[non_exhaustive]
+pub enum Fact {
+    AttributeAdjust {
+        text: Option<String>,
+        icon: Option<String>,
+        value: f32,
+        target: AttributeType,
+    },
+    Buff {
+        text: Option<String>,
+        icon: Option<String>,
+        status: Option<String>,
+        description: Option<String>,
+        apply_count: Option<u8>,
+        duration: Option<f32>,
+    },
+    BuffConversion {
+        text: Option<String>,
+        icon: Option<String>,
+        source: AttributeType,
+        target: AttributeType,
+        percent: f32,
+    },
+    ComboField {
+        text: Option<String>,
+        icon: Option<String>,
+        field_type: ComboFieldType,
+        duration: Option<f32>,
+    },
+    ComboFinisher {
+        text: Option<String>,
+        icon: Option<String>,
+        finisher_type: ComboFinisherType,
+        percent: f32,
+    },
+    Damage {
+        text: Option<String>,
+        icon: Option<String>,
+        hit_count: u16,
+        dmg_multiplier: f32,
+    },
+    Distance {
+        text: Option<String>,
+        icon: Option<String>,
+        distance: u32,
+    },
+    Duration {
+        text: Option<String>,
+        icon: Option<String>,
+        duration: f32,
+    },
+    Heal {
+        text: Option<String>,
+        icon: Option<String>,
+        hit_count: u16,
+    },
+    HealingAdjust {
+        text: Option<String>,
+        icon: Option<String>,
+        hit_count: u16,
+    },
+    NoData {
+        text: Option<String>,
+        icon: Option<String>,
+    },
+    Number {
+        text: Option<String>,
+        icon: Option<String>,
+        value: f32,
+    },
+    Percent {
+        text: Option<String>,
+        icon: Option<String>,
+        percent: f32,
+    },
+    PrefixedBuff {
+        text: Option<String>,
+        icon: Option<String>,
+        status: Option<String>,
+        description: Option<String>,
+        apply_count: Option<u8>,
+        duration: Option<f32>,
+        prefix: FactPrefix,
+    },
+    Radius {
+        text: Option<String>,
+        icon: Option<String>,
+        distance: u32,
+    },
+    Range {
+        text: Option<String>,
+        icon: Option<String>,
+        value: u32,
+    },
+    Recharge {
+        text: Option<String>,
+        icon: Option<String>,
+        value: f32,
+    },
+    StunBreak {
+        text: Option<String>,
+        icon: Option<String>,
+        value: bool,
+    },
+    Time {
+        text: Option<String>,
+        icon: Option<String>,
+        duration: f32,
+    },
+    Unblockable {
+        text: Option<String>,
+        icon: Option<String>,
+        value: bool,
+    },
+    /// a fact type that is not known to this library, or a known one with
+    /// unknown fields, including its `type`
+    #[serde(skip)]
+    Unknown(serde_json::Map<String, serde_json::Value>),
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+impl Serialize for Fact {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Fact::Unknown(fact) => fact.serialize(serializer),
+            fact => Fact::serialize(fact, serializer),
+        }
+    }
+}
+
+// facts of unknown types are kept as they are, while errors in known ones
+// still surface
+impl<'de> Deserialize<'de> for Fact {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let fact = serde_json::Map::deserialize(deserializer)?;
+        if let Some(_type) = fact.get("type") {
+            let _type = FactsType::deserialize(_type).map_err(D::Error::custom)?;
+            if let FactsType::Unknown(_) = _type {
+                return Ok(Fact::Unknown(fact));
+            }
+        }
+        let known =
+            Fact::deserialize(serde_json::Value::Object(fact.clone())).map_err(D::Error::custom)?;
+
+        // serializing writes every modelled field, whatever is missing there
+        // was dropped while deserializing
+        let fields =
+            Fact::serialize(&known, serde_json::value::Serializer).map_err(D::Error::custom)?;
+        match fact.keys().find(|key| fields.get(key.as_str()).is_none()) {
+            #[cfg(feature = "strict")]
+            Some(field) => Err(D::Error::custom(format_args!("unknown field `{field}`"))),
+            #[cfg(not(feature = "strict"))]
+            Some(_) => Ok(Fact::Unknown(fact)),
+            None => Ok(known),
+        }
+    }
+}
+
+// no extra, `Fact` takes all remaining fields and is strict on its own
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct TraitedFact {
     #[serde(flatten)]
     pub fact: Fact,
@@ -75,3 +303,58 @@ pub struct TraitedFact {
     /// array index of Fact
     pub overrides: Option<u8>,
 }
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Endpoint)]
+#[endpoint(
+    url = "v2/skills",
+    version = "2022-07-22T00:00:00.000Z",
+    locale,
+    bulk(all = false),
+    id = "id"
+)]
+pub struct Skill {
+    pub id: SkillId,
+    pub name: String,
+    pub description: Option<String>,
+    pub icon: Option<String>,
+    pub chat_link: String,
+    #[serde(rename = "type")]
+    pub _type: Option<SkillType>,
+    pub weapon_type: Option<WeaponType>,
+    #[serde(default)]
+    pub professions: Vec<Profession>,
+    pub slot: Option<Slot>,
+    #[serde(default)]
+    pub facts: Vec<Fact>,
+    #[serde(default)]
+    pub traited_facts: Vec<TraitedFact>,
+    #[serde(default)]
+    pub categories: Vec<String>,
+    #[serde(default)]
+    pub flags: Vec<String>,
+    pub specialization: Option<SpecializationId>,
+    /// the elementalist attunement this skill belongs to
+    pub attunement: Option<Attunement>,
+    /// energy cost of revenant skills
+    pub cost: Option<u8>,
+    /// the offhand weapon of thief dual wield skills
+    pub dual_wield: Option<WeaponType>,
+    /// the skill this one turns into after being used
+    pub flip_skill: Option<SkillId>,
+    /// initiative cost of thief skills
+    pub initiative: Option<u8>,
+    /// the next skill of an auto attack chain
+    pub next_chain: Option<SkillId>,
+    /// the previous skill of an auto attack chain
+    pub prev_chain: Option<SkillId>,
+    /// the skills of the form this skill transforms into
+    #[serde(default)]
+    pub transform_skills: Vec<SkillId>,
+    /// the skills of the bundle this skill equips
+    #[serde(default)]
+    pub bundle_skills: Vec<SkillId>,
+    /// the engineer toolbelt skill that comes with this one
+    pub toolbelt_skill: Option<SkillId>,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
+}
@@ -1 +1,32 @@
-pub type SpecializationId = u16;
+use serde::{Deserialize, Serialize};
+
+use crate::{authenticated::characters::Profession, game_mechanics::traits::TraitId, Endpoint};
+
+id!(SpecializationId(u16));
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Endpoint)]
+#[endpoint(
+    url = "v2/specializations",
+    version = "2022-07-22T00:00:00.000Z",
+    locale,
+    bulk,
+    id = "id"
+)]
+pub struct Specialization {
+    pub id: SpecializationId,
+    pub name: String,
+    pub profession: Profession,
+    pub elite: bool,
+    pub minor_traits: Vec<TraitId>,
+    pub major_traits: Vec<TraitId>,
+    /// the trait unlocking the weapon of elite specializations
+    pub weapon_trait: Option<TraitId>,
+    pub icon: String,
+    pub background: String,
+    /// only set for elite specializations
+    pub profession_icon: Option<String>,
+    /// only set for elite specializations
+    pub profession_icon_big: Option<String>,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
+}
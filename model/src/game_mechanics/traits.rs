@@ -1 +1 @@
-pub type TraitId = u16;
+id!(TraitId(u16));
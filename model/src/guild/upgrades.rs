@@ -1 +1 @@
-pub type GuildUpgradeId = u64;
+id!(GuildUpgradeId(u64));
@@ -2,7 +2,9 @@ pub mod itemstats;
 pub mod recipes;
 pub mod skins;
 
-use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::{
     game_mechanics::skills::SkillId,
@@ -12,9 +14,10 @@ use crate::{
     BulkEndpoint, Endpoint, EndpointWithId,
 };
 
-pub type ItemId = u32;
+id!(ItemId(u32));
 
 #[derive(Clone, PartialEq, Eq, PartialOrd, Debug, Serialize, Deserialize)]
+#[non_exhaustive]
 pub enum ItemType {
     Armor,
     Back,
@@ -34,9 +37,12 @@ pub enum ItemType {
     Key,
     Qux,
     Quux,
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 #[derive(Clone, PartialEq, Eq, PartialOrd, Debug, Serialize, Deserialize)]
+#[non_exhaustive]
 pub enum Rarity {
     Junk,
     Basic,
@@ -46,9 +52,12 @@ pub enum Rarity {
     Exotic,
     Ascended,
     Legendary,
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 #[derive(Clone, PartialEq, Eq, PartialOrd, Debug, Serialize, Deserialize)]
+#[non_exhaustive]
 pub enum Flags {
     AccountBindOnUse,
     AccountBound,
@@ -67,9 +76,12 @@ pub enum Flags {
     SoulBindOnUse,
     Tonic,
     Unique,
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 #[derive(Clone, PartialEq, Eq, PartialOrd, Debug, Serialize, Deserialize)]
+#[non_exhaustive]
 pub enum GameTypes {
     Activity,
     Dungeon,
@@ -77,9 +89,12 @@ pub enum GameTypes {
     Pvp,
     PvpLobby,
     Wvw,
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 #[derive(Clone, PartialEq, Eq, PartialOrd, Debug, Serialize, Deserialize)]
+#[non_exhaustive]
 pub enum Restrictions {
     Asura,
     Charr,
@@ -95,9 +110,12 @@ pub enum Restrictions {
     Thief,
     Warrior,
     Female,
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 #[derive(Clone, PartialEq, Eq, PartialOrd, Debug, Serialize, Deserialize)]
+#[non_exhaustive]
 pub enum ArmorSlot {
     Boots,
     Coat,
@@ -106,29 +124,42 @@ pub enum ArmorSlot {
     HelmAquatic,
     Leggings,
     Shoulders,
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 #[derive(Clone, PartialEq, Eq, PartialOrd, Debug, Serialize, Deserialize)]
+#[non_exhaustive]
 pub enum WeightClass {
     Heavy,
     Medium,
     Light,
     Clothing,
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 #[derive(Clone, PartialEq, Eq, PartialOrd, Debug, Serialize, Deserialize)]
+#[non_exhaustive]
 pub enum InfusionType {
     Enrichment,
     Infusion,
+    #[serde(untagged)]
+    Unknown(String),
 }
 
-#[derive(Clone, PartialEq, Eq, PartialOrd, Debug, Serialize, Deserialize)]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub struct InfusionSlot {
     pub flags: Vec<InfusionType>,
     pub item_id: Option<ItemId>,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
 }
 
+partial_ord_without_extra!(InfusionSlot { flags, item_id });
+
 #[derive(Clone, PartialEq, PartialOrd, Debug, Serialize, Deserialize, Ord, Eq, Hash)]
+#[non_exhaustive]
 pub enum AttributeType {
     AgonyResistance,
     BoonDuration,
@@ -140,27 +171,51 @@ pub enum AttributeType {
     Precision,
     Toughness,
     Vitality,
+    #[serde(untagged)]
+    Unknown(String),
 }
 
-#[derive(Clone, PartialEq, Eq, PartialOrd, Debug, Serialize, Deserialize)]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub struct Attribute {
     pub attribute: AttributeType,
     pub modifier: u16,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
 }
 
-#[derive(Clone, PartialEq, Eq, PartialOrd, Debug, Serialize, Deserialize)]
+partial_ord_without_extra!(Attribute {
+    attribute,
+    modifier
+});
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub struct Buff {
     pub skill_id: SkillId,
     pub description: Option<String>,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
 }
 
-#[derive(Clone, PartialEq, Eq, PartialOrd, Debug, Serialize, Deserialize)]
+partial_ord_without_extra!(Buff {
+    skill_id,
+    description
+});
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub struct InfixUpgrade {
     pub id: StatsId,
     pub attributes: Vec<Attribute>,
     pub buff: Option<Buff>,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
 }
 
+partial_ord_without_extra!(InfixUpgrade {
+    id,
+    attributes,
+    buff
+});
+
 #[derive(Clone, PartialEq, PartialOrd, Debug, Serialize, Deserialize)]
 pub struct Upgrades {
     pub attribute_adjustment: f32,
@@ -171,8 +226,7 @@ pub struct Upgrades {
     pub stat_choices: Option<Vec<StatsId>>,
 }
 
-#[derive(Clone, PartialEq, PartialOrd, Debug, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub struct ArmorDetails {
     #[serde(rename = "type")]
     pub _type: ArmorSlot,
@@ -180,23 +234,42 @@ pub struct ArmorDetails {
     pub defense: u16,
     #[serde(flatten)]
     pub upgrades: Upgrades,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
 }
 
-#[derive(Clone, PartialEq, PartialOrd, Debug, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+partial_ord_without_extra!(ArmorDetails {
+    _type,
+    weight_class,
+    defense,
+    upgrades
+});
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub struct BackItemDetails {
     #[serde(flatten)]
     pub upgrades: Upgrades,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
 }
 
-#[derive(Clone, PartialEq, Eq, PartialOrd, Debug, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+partial_ord_without_extra!(BackItemDetails { upgrades });
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub struct BagDetails {
     pub size: u8,
     pub no_sell_or_sort: bool,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
 }
 
+partial_ord_without_extra!(BagDetails {
+    size,
+    no_sell_or_sort
+});
+
 #[derive(Clone, PartialEq, Eq, PartialOrd, Debug, Serialize, Deserialize)]
+#[non_exhaustive]
 pub enum ConsumableType {
     AppearanceChange,
     Booze,
@@ -213,9 +286,12 @@ pub enum ConsumableType {
     UpgradeRemoval,
     Utility,
     TeleportToFriend,
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 #[derive(Clone, PartialEq, Eq, PartialOrd, Debug, Serialize, Deserialize)]
+#[non_exhaustive]
 pub enum UnlockType {
     BagSlot,
     BankTab,
@@ -234,10 +310,11 @@ pub enum UnlockType {
     Outfit,
     RandomUnlock,
     SharedSlot,
+    #[serde(untagged)]
+    Unknown(String),
 }
 
-#[derive(Clone, PartialEq, Eq, PartialOrd, Debug, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub struct ConsumableDetails {
     #[serde(rename = "type")]
     pub _type: ConsumableType,
@@ -252,99 +329,157 @@ pub struct ConsumableDetails {
     pub name: Option<String>,
     pub icon: Option<String>,
     pub skins: Option<Vec<u64>>,
-}
+    #[serde(flatten)]
+    pub extra: crate::Extra,
+}
+
+partial_ord_without_extra!(ConsumableDetails {
+    _type,
+    description,
+    duration_ms,
+    unlock_type,
+    color_id,
+    recipe_id,
+    extra_recipe_ids,
+    guild_upgrade_id,
+    apply_count,
+    name,
+    icon,
+    skins
+});
 
 #[derive(Clone, PartialEq, Eq, PartialOrd, Debug, Serialize, Deserialize)]
+#[non_exhaustive]
 pub enum ContainerType {
     Default,
     GiftBox,
     Immediate,
     OpenUI,
+    #[serde(untagged)]
+    Unknown(String),
 }
 
-#[derive(Clone, PartialEq, Eq, PartialOrd, Debug, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub struct ContainerDetails {
     #[serde(rename = "type")]
     pub _type: ContainerType,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
 }
 
+partial_ord_without_extra!(ContainerDetails { _type });
+
 #[derive(Clone, PartialEq, Eq, PartialOrd, Debug, Serialize, Deserialize)]
+#[non_exhaustive]
 pub enum GatheringToolsType {
     Foraging,
     Logging,
     Mining,
     Foo,
+    #[serde(untagged)]
+    Unknown(String),
 }
 
-#[derive(Clone, PartialEq, Eq, PartialOrd, Debug, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub struct GatheringToolsDetails {
     #[serde(rename = "type")]
     pub _type: GatheringToolsType,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
 }
 
+partial_ord_without_extra!(GatheringToolsDetails { _type });
+
 #[derive(Clone, PartialEq, Eq, PartialOrd, Debug, Serialize, Deserialize)]
+#[non_exhaustive]
 pub enum GizmoType {
     Default,
     ContainerKey,
     RentableContractNpc,
     UnlimitedConsumable,
+    #[serde(untagged)]
+    Unknown(String),
 }
 
-#[derive(Clone, PartialEq, Eq, PartialOrd, Debug, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub struct GizmoDetails {
     #[serde(rename = "type")]
     pub _type: GizmoType,
     pub guild_upgrade_id: Option<GuildUpgradeId>,
     pub vendor_ids: Option<Vec<u64>>, // TODO: figure out if this is resolvable
+    #[serde(flatten)]
+    pub extra: crate::Extra,
 }
 
-#[derive(Clone, PartialEq, Eq, PartialOrd, Debug, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+partial_ord_without_extra!(GizmoDetails {
+    _type,
+    guild_upgrade_id,
+    vendor_ids
+});
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub struct MiniatureDetails {
     pub minipet_id: MiniPetId,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
 }
 
+partial_ord_without_extra!(MiniatureDetails { minipet_id });
+
 #[derive(Clone, PartialEq, Eq, PartialOrd, Debug, Serialize, Deserialize)]
+#[non_exhaustive]
 pub enum SalvageKitType {
     Salvage,
+    #[serde(untagged)]
+    Unknown(String),
 }
 
-#[derive(Clone, PartialEq, Eq, PartialOrd, Debug, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub struct SalvageKitDetails {
     #[serde(rename = "type")]
     pub _type: SalvageKitType,
     pub charges: u8,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
 }
 
+partial_ord_without_extra!(SalvageKitDetails { _type, charges });
+
 #[derive(Clone, PartialEq, Eq, PartialOrd, Debug, Serialize, Deserialize)]
+#[non_exhaustive]
 pub enum TrinketType {
     Accessory,
     Amulet,
     Ring,
+    #[serde(untagged)]
+    Unknown(String),
 }
 
-#[derive(Clone, PartialEq, PartialOrd, Debug, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub struct TrinketDetails {
     #[serde(rename = "type")]
     pub _type: TrinketType,
     #[serde(flatten)]
     pub upgrades: Upgrades,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
 }
 
+partial_ord_without_extra!(TrinketDetails { _type, upgrades });
+
 #[derive(Clone, PartialEq, Eq, PartialOrd, Debug, Serialize, Deserialize)]
+#[non_exhaustive]
 pub enum UpgradeComponentType {
     Default,
     Gem,
     Rune,
     Sigil,
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 #[derive(Clone, PartialEq, Eq, PartialOrd, Debug, Serialize, Deserialize)]
+#[non_exhaustive]
 pub enum UpgradeComponentFlags {
     Axe,
     Dagger,
@@ -369,9 +504,12 @@ pub enum UpgradeComponentFlags {
     MediumArmor,
     LightArmor,
     Trinket,
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 #[derive(Clone, PartialEq, Eq, PartialOrd, Debug, Serialize, Deserialize)]
+#[non_exhaustive]
 pub enum InfusionUpgradeFlags {
     Enrichment,
     Infusion,
@@ -379,10 +517,11 @@ pub enum InfusionUpgradeFlags {
     Offense,
     Utility,
     Agony,
+    #[serde(untagged)]
+    Unknown(String),
 }
 
-#[derive(Clone, PartialEq, PartialOrd, Debug, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub struct UpgradeComponentDetails {
     #[serde(rename = "type")]
     pub _type: UpgradeComponentType,
@@ -392,9 +531,22 @@ pub struct UpgradeComponentDetails {
     pub attribute_adjustment: f32,
     pub infix_upgrade: InfixUpgrade,
     pub bonuses: Option<Vec<String>>,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
 }
 
+partial_ord_without_extra!(UpgradeComponentDetails {
+    _type,
+    flags,
+    infusion_upgrade_flags,
+    suffix,
+    attribute_adjustment,
+    infix_upgrade,
+    bonuses
+});
+
 #[derive(Clone, PartialEq, Eq, PartialOrd, Debug, Serialize, Deserialize)]
+#[non_exhaustive]
 pub enum WeaponType {
     Axe,
     Dagger,
@@ -420,19 +572,23 @@ pub enum WeaponType {
     Toy,
     ToyTwoHanded,
     None,
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 #[derive(Clone, PartialEq, Eq, PartialOrd, Debug, Serialize, Deserialize)]
+#[non_exhaustive]
 pub enum DamageType {
     Fire,
     Ice,
     Lightning,
     Physical,
     Choking,
+    #[serde(untagged)]
+    Unknown(String),
 }
 
-#[derive(Clone, PartialEq, PartialOrd, Debug, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub struct WeaponDetails {
     #[serde(rename = "type")]
     pub _type: WeaponType,
@@ -442,10 +598,21 @@ pub struct WeaponDetails {
     pub defense: u16,
     #[serde(flatten)]
     pub upgrades: Upgrades,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
 }
 
+partial_ord_without_extra!(WeaponDetails {
+    _type,
+    damage_type,
+    min_power,
+    max_power,
+    defense,
+    upgrades
+});
+
 #[derive(Clone, PartialEq, PartialOrd, Debug, Serialize, Deserialize)]
-#[serde(tag = "type", content = "details")]
+#[serde(remote = "Self", tag = "type", content = "details")]
 pub enum Details {
     Armor(ArmorDetails),
     Back(BackItemDetails),
@@ -465,6 +632,66 @@ pub enum Details {
     Key,
     Qux,
     Quux,
+    #[serde(skip)]
+    Unknown(UnknownDetails),
+}
+
+// `Details` is flattened into `Item`, so it has to be deserialized as a struct
+// to leave the remaining fields to `Item::extra`
+#[derive(Deserialize)]
+struct TaggedDetails {
+    #[serde(rename = "type")]
+    _type: ItemType,
+    details: Option<serde_json::Value>,
+}
+
+impl Serialize for Details {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Details::Unknown(details) => details.serialize(serializer),
+            details => Details::serialize(details, serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Details {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let tagged = TaggedDetails::deserialize(deserializer)?;
+        if let ItemType::Unknown(_type) = tagged._type {
+            return Ok(Details::Unknown(UnknownDetails {
+                _type,
+                details: tagged.details,
+            }));
+        }
+
+        let mut map = serde_json::Map::new();
+        map.insert(
+            "type".to_string(),
+            serde_json::to_value(tagged._type).map_err(D::Error::custom)?,
+        );
+        if let Some(details) = tagged.details {
+            map.insert("details".to_string(), details);
+        }
+        Details::deserialize(serde_json::Value::Object(map)).map_err(D::Error::custom)
+    }
+}
+
+/// details of an item type that is not known to this library
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct UnknownDetails {
+    #[serde(rename = "type")]
+    pub _type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<serde_json::Value>,
+}
+
+impl PartialOrd for UnknownDetails {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match self._type.cmp(&other._type) {
+            Ordering::Equal if self.details != other.details => None,
+            ord => Some(ord),
+        }
+    }
 }
 
 impl From<Details> for ItemType {
@@ -488,11 +715,12 @@ impl From<Details> for ItemType {
             Details::Key => ItemType::Key,
             Details::Qux => ItemType::Qux,
             Details::Quux => ItemType::Quux,
+            Details::Unknown(d) => ItemType::Unknown(d._type),
         }
     }
 }
 
-#[derive(Clone, PartialEq, PartialOrd, Debug, Serialize, Deserialize)]
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub struct Item {
     pub id: ItemId,
     pub chat_link: String,
@@ -508,7 +736,25 @@ pub struct Item {
     pub restrictions: Vec<Restrictions>,
     #[serde(flatten)]
     pub details: Details,
-}
+    #[serde(flatten)]
+    pub extra: crate::Extra,
+}
+
+partial_ord_without_extra!(Item {
+    id,
+    chat_link,
+    name,
+    icon,
+    description,
+    rarity,
+    level,
+    vendor_value,
+    default_skin,
+    flags,
+    game_types,
+    restrictions,
+    details
+});
 
 impl EndpointWithId for Item {
     type IdType = ItemId;
@@ -1 +1 @@
-pub type StatsId = u32;
+id!(StatsId(u32));
@@ -1,11 +1,16 @@
-pub type RecipeId = u32;
+id!(RecipeId(u32));
 
 use serde::{Deserialize, Serialize};
 
 pub use crate::authenticated::characters::Discipline;
-use crate::{items::ItemId, BulkEndpoint, Endpoint, EndpointWithId};
+use crate::{
+    items::ItemId,
+    references::{ReferenceSet, References},
+    BulkEndpoint, Endpoint, EndpointWithId, QueryEndpoint,
+};
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum RecipeType {
     Axe,
     Dagger,
@@ -60,24 +65,39 @@ pub enum RecipeType {
     Dye,
     Potion,
     UpgradeComponent,
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum RecipeFlag {
     AutoLearned,
     LearnedFromItem,
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Ingredient {
     pub item_id: ItemId,
     pub count: u16,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
+}
+
+impl References for Ingredient {
+    fn references(&self, set: &mut ReferenceSet) {
+        self.item_id.references(set);
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct GuildIngredient {
     pub upgrade_id: u32,
     pub count: u16,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -95,6 +115,15 @@ pub struct Recipe {
     guild_ingredients: Option<Vec<GuildIngredient>>,
     output_upgrade_id: Option<u32>,
     chat_link: String,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
+}
+
+impl References for Recipe {
+    fn references(&self, set: &mut ReferenceSet) {
+        self.output_item_id.references(set);
+        self.ingredients.references(set);
+    }
 }
 
 impl EndpointWithId for Recipe {
@@ -114,3 +143,46 @@ impl BulkEndpoint for Recipe {
         &self.id
     }
 }
+
+/// ids of the recipes that use or create an item
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct RecipeSearch(pub Vec<RecipeId>);
+
+/// parameters of [`RecipeSearch`], only one of them may be set
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize)]
+pub struct RecipeSearchQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input: Option<ItemId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<ItemId>,
+}
+
+impl RecipeSearchQuery {
+    /// recipes that use the item as an ingredient
+    pub fn input(item: impl Into<ItemId>) -> Self {
+        Self {
+            input: Some(item.into()),
+            output: None,
+        }
+    }
+
+    /// recipes that create the item
+    pub fn output(item: impl Into<ItemId>) -> Self {
+        Self {
+            input: None,
+            output: Some(item.into()),
+        }
+    }
+}
+
+impl Endpoint for RecipeSearch {
+    const AUTHENTICATED: bool = false;
+    const LOCALE: bool = false;
+    const URL: &'static str = "v2/recipes/search";
+    const VERSION: &'static str = "2021-01-11T00:00:00.000Z";
+}
+
+impl QueryEndpoint for RecipeSearch {
+    type Query = RecipeSearchQuery;
+}
@@ -1 +1 @@
-pub type SkinId = u32;
+id!(SkinId(u32));
@@ -1,6 +1,38 @@
-use std::fmt::Display;
+use std::{
+    fmt::Display,
+    hash::Hash,
+    ops::{Deref, DerefMut},
+    str::FromStr,
+};
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::authenticated::Permissions;
+
+// lets the derive macros refer to this crate by name from inside it
+extern crate self as gw2lib_model;
+
+#[macro_use]
+mod id;
+
+/// orders a struct by its fields in the given order, leaving out `extra`
+///
+/// structs that only differ in their [`Extra`] fields are not comparable
+macro_rules! partial_ord_without_extra {
+    ($name:ident { $($field:ident),* }) => {
+        impl PartialOrd for $name {
+            fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                $(
+                    match self.$field.partial_cmp(&other.$field) {
+                        Some(std::cmp::Ordering::Equal) => {}
+                        ord => return ord,
+                    }
+                )*
+                (self.extra == other.extra).then_some(std::cmp::Ordering::Equal)
+            }
+        }
+    };
+}
 
 pub mod authenticated;
 pub mod game_mechanics;
@@ -8,6 +40,8 @@ pub mod guild;
 pub mod items;
 pub mod misc;
 pub mod pvp;
+pub mod references;
+pub mod registry;
 pub mod tradingpost;
 pub mod wvw;
 
@@ -38,6 +72,35 @@ impl Language {
     }
 }
 
+impl FromStr for Language {
+    type Err = UnknownLanguage;
+
+    /// parses the language code, unlike [`Language::from`] this does not fall
+    /// back to english
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "en" => Ok(Language::En),
+            "fr" => Ok(Language::Fr),
+            "de" => Ok(Language::De),
+            "es" => Ok(Language::Es),
+            "zh" => Ok(Language::Zh),
+            _ => Err(UnknownLanguage(s.to_string())),
+        }
+    }
+}
+
+/// error returned when parsing an unsupported language code
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownLanguage(pub String);
+
+impl Display for UnknownLanguage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "unknown language: {}", self.0)
+    }
+}
+
+impl std::error::Error for UnknownLanguage {}
+
 impl From<&str> for Language {
     fn from(v: &str) -> Self {
         match v {
@@ -51,8 +114,69 @@ impl From<&str> for Language {
     }
 }
 
+#[cfg(feature = "chrono")]
+pub type TimeStamp = chrono::DateTime<chrono::Utc>;
+/// rfc3339 as sent by the api, the `chrono` feature parses it
+#[cfg(not(feature = "chrono"))]
 pub type TimeStamp = String;
 
+/// fields returned by the api that are not part of the model
+///
+/// collected on every struct. The `strict` feature rejects unknown fields
+/// instead, so it is always empty then.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(transparent)]
+pub struct Extra(pub serde_json::Map<String, serde_json::Value>);
+
+impl<'de> Deserialize<'de> for Extra {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let extra = serde_json::Map::deserialize(deserializer)?;
+        #[cfg(feature = "strict")]
+        if let Some(field) = extra.keys().next() {
+            return Err(serde::de::Error::custom(format_args!(
+                "unknown field `{field}`"
+            )));
+        }
+        Ok(Extra(extra))
+    }
+}
+
+impl Deref for Extra {
+    type Target = serde_json::Map<String, serde_json::Value>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for Extra {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+/// derives the endpoint traits, see the `gw2lib_derive` crate for the
+/// arguments
+/// ## Example
+/// ```
+/// use gw2lib_model::{items::ItemId, Endpoint};
+/// use serde::Deserialize;
+///
+/// #[derive(Clone, Deserialize, Endpoint)]
+/// #[endpoint(
+///     url = "v2/items",
+///     version = "2022-07-22T00:00:00.000Z",
+///     locale,
+///     bulk,
+///     id = "id"
+/// )]
+/// pub struct Item {
+///     pub id: ItemId,
+///     pub name: String,
+/// }
+/// ```
+pub use gw2lib_derive::Endpoint;
+
 pub trait Endpoint: Sized {
     /// whether this endpoint requires authentication
     const AUTHENTICATED: bool;
@@ -61,6 +185,9 @@ pub trait Endpoint: Sized {
     const LOCALE: bool;
 
     /// endpoint url in the format `v2/account`
+    ///
+    /// nested endpoints contain a `:placeholder` for every parent, e.g.
+    /// `v2/continents/:continent/floors`, see [EndpointWithId::parents]
     /// ### Remarks
     /// Among other things, this URL is used to fetch ids.
     /// `v2/characters/My Character/core` still requires `v2/characters` to be
@@ -70,14 +197,34 @@ pub trait Endpoint: Sized {
 
     /// version of the endpoint to request
     const VERSION: &'static str;
+
+    /// permissions the api key needs to access this endpoint
+    const PERMISSIONS: &'static [Permissions] = &[];
 }
 
 pub trait EndpointWithId: Endpoint {
+    /// the id of this endpoint, e.g. [`items::ItemId`]
     type IdType: Display;
 
     fn format_url(id: &str) -> String {
         format!("{}/{}", Self::URL, id)
     }
+
+    /// the path segments replacing the placeholders in [Endpoint::URL],
+    /// outermost first
+    ///
+    /// only nested endpoints have any, usually `id.parents()` of a [`Nested`]
+    /// id
+    fn parents(id: &Self::IdType) -> Vec<String> {
+        let _ = id;
+        Vec::new()
+    }
+
+    /// copies the parents of `id` onto an entity that was requested below
+    /// them, the api only returns the id of the entity itself
+    fn set_parents(&mut self, id: &Self::IdType) {
+        let _ = id;
+    }
 }
 
 pub trait FixedEndpoint: Endpoint {}
@@ -91,4 +238,81 @@ pub trait BulkEndpoint: EndpointWithId {
 
 pub trait PagedEndpoint: Endpoint {}
 
+/// endpoints that are requested with query parameters, e.g.
+/// `v2/recipes/search?input=`
+pub trait QueryEndpoint: Endpoint {
+    /// the query parameters, serialized as `key=value` pairs
+    type Query: Serialize + Hash + Send + Sync + 'static;
+}
+
 impl<T: BulkEndpoint> PagedEndpoint for T {}
+
+/// ids that are part of a url path
+pub trait PathId {
+    /// appends the path segments of this id, outermost first
+    fn segments(&self, segments: &mut Vec<String>);
+}
+
+impl PathId for String {
+    fn segments(&self, segments: &mut Vec<String>) {
+        segments.push(self.clone());
+    }
+}
+
+/// the id of an entity nested below another entity, e.g. a floor of a
+/// continent
+///
+/// displays and (de)serializes as the id of the entity itself, because that's
+/// all the api returns. Deserialized ids have a default `parent` until the
+/// client copies the requested one over.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Nested<P, I> {
+    pub parent: P,
+    pub id: I,
+}
+
+impl<P, I> Nested<P, I> {
+    pub fn new(parent: impl Into<P>, id: impl Into<I>) -> Self {
+        Self {
+            parent: parent.into(),
+            id: id.into(),
+        }
+    }
+}
+
+impl<P: PathId, I> Nested<P, I> {
+    /// the path segments of all parents, outermost first
+    pub fn parents(&self) -> Vec<String> {
+        let mut segments = Vec::new();
+        self.parent.segments(&mut segments);
+        segments
+    }
+}
+
+impl<P: PathId, I: Display> PathId for Nested<P, I> {
+    fn segments(&self, segments: &mut Vec<String>) {
+        self.parent.segments(segments);
+        segments.push(self.id.to_string());
+    }
+}
+
+impl<P, I: Display> Display for Nested<P, I> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.id.fmt(f)
+    }
+}
+
+impl<P, I: Serialize> Serialize for Nested<P, I> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.id.serialize(serializer)
+    }
+}
+
+impl<'de, P: Default, I: Deserialize<'de>> Deserialize<'de> for Nested<P, I> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Self {
+            parent: P::default(),
+            id: I::deserialize(deserializer)?,
+        })
+    }
+}
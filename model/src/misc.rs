@@ -1,5 +1,6 @@
 pub mod build;
 pub mod colors;
+pub mod continents;
 pub mod currencies;
 pub mod minis;
 pub mod titles;
@@ -2,11 +2,15 @@ use serde::{Deserialize, Serialize};
 
 use crate::{Endpoint, FixedEndpoint};
 
-#[derive(Clone, PartialEq, Eq, PartialOrd, Debug, Serialize, Deserialize)]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub struct Build {
     pub id: u64,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
 }
 
+partial_ord_without_extra!(Build { id });
+
 impl Endpoint for Build {
     const AUTHENTICATED: bool = false;
     const LOCALE: bool = false;
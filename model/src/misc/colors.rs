@@ -1,10 +1,14 @@
 use either::Either;
 use serde::{Deserialize, Serialize};
 
-use crate::{items::ItemId, BulkEndpoint, Endpoint, EndpointWithId};
+use crate::{
+    items::ItemId,
+    references::{ReferenceSet, References},
+    BulkEndpoint, Endpoint, EndpointWithId,
+};
 
 pub type RGB = (u8, u8, u8);
-pub type ColorId = u16;
+id!(ColorId(u16));
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct MaterialDetails {
@@ -14,9 +18,12 @@ pub struct MaterialDetails {
     pub saturation: f32,
     pub lightness: f32,
     pub rgb: RGB,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum Hue {
     Gray,
     Brown,
@@ -26,22 +33,30 @@ pub enum Hue {
     Green,
     Blue,
     Purple,
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum Material {
     Vibrant,
     Leather,
     Metal,
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum Rarity {
     Starter,
     Common,
     Uncommon,
     Rare,
     Exclusive,
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -58,6 +73,14 @@ pub struct Color {
     /// is only `Right` for Dye Remover
     #[serde(with = "either::serde_untagged")]
     pub categories: Either<(Hue, Material, Rarity), [(); 0]>,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
+}
+
+impl References for Color {
+    fn references(&self, set: &mut ReferenceSet) {
+        self.item.references(set);
+    }
 }
 
 impl EndpointWithId for Color {
@@ -0,0 +1,277 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+id!(ContinentId(u32));
+id!(FloorId(i16));
+id!(RegionId(u32));
+id!(MapId(u32));
+id!(PointOfInterestId(u32));
+id!(TaskId(u32));
+id!(SectorId(u32));
+
+/// a floor of a continent
+pub type FloorPath = Nested<ContinentId, FloorId>;
+/// a region on a floor
+pub type RegionPath = Nested<FloorPath, RegionId>;
+/// a map in a region
+pub type MapPath = Nested<RegionPath, MapId>;
+
+pub type Coordinates = [f32; 2];
+/// top left and bottom right corner
+pub type Rect = [Coordinates; 2];
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Continent {
+    pub id: ContinentId,
+    pub name: String,
+    pub continent_dims: (u32, u32),
+    pub min_zoom: u8,
+    pub max_zoom: u8,
+    pub floors: Vec<FloorId>,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
+}
+
+impl Endpoint for Continent {
+    const AUTHENTICATED: bool = false;
+    const LOCALE: bool = true;
+    const URL: &'static str = "v2/continents";
+    const VERSION: &'static str = "2022-07-22T00:00:00.000Z";
+}
+
+impl EndpointWithId for Continent {
+    type IdType = ContinentId;
+}
+
+impl BulkEndpoint for Continent {
+    const ALL: bool = true;
+
+    fn id(&self) -> &Self::IdType {
+        &self.id
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Floor {
+    pub texture_dims: (u32, u32),
+    pub clamped_view: Option<Rect>,
+    pub regions: HashMap<RegionId, Region>,
+    pub id: FloorPath,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
+}
+
+impl Endpoint for Floor {
+    const AUTHENTICATED: bool = false;
+    const LOCALE: bool = true;
+    const URL: &'static str = "v2/continents/:continent/floors";
+    const VERSION: &'static str = "2022-07-22T00:00:00.000Z";
+}
+
+impl EndpointWithId for Floor {
+    type IdType = FloorPath;
+
+    fn parents(id: &Self::IdType) -> Vec<String> {
+        id.parents()
+    }
+
+    fn set_parents(&mut self, id: &Self::IdType) {
+        self.id.parent = id.parent;
+        for region in self.regions.values_mut() {
+            region.set_parents(&Nested::new(self.id, region.id.id));
+        }
+    }
+}
+
+impl BulkEndpoint for Floor {
+    const ALL: bool = false;
+
+    fn id(&self) -> &Self::IdType {
+        &self.id
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Region {
+    pub name: String,
+    pub label_coord: Coordinates,
+    pub continent_rect: Rect,
+    pub maps: HashMap<MapId, Map>,
+    pub id: RegionPath,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
+}
+
+impl Endpoint for Region {
+    const AUTHENTICATED: bool = false;
+    const LOCALE: bool = true;
+    const URL: &'static str = "v2/continents/:continent/floors/:floor/regions";
+    const VERSION: &'static str = "2022-07-22T00:00:00.000Z";
+}
+
+impl EndpointWithId for Region {
+    type IdType = RegionPath;
+
+    fn parents(id: &Self::IdType) -> Vec<String> {
+        id.parents()
+    }
+
+    fn set_parents(&mut self, id: &Self::IdType) {
+        self.id.parent = id.parent;
+        for map in self.maps.values_mut() {
+            map.set_parents(&Nested::new(self.id, map.id.id));
+        }
+    }
+}
+
+impl BulkEndpoint for Region {
+    const ALL: bool = false;
+
+    fn id(&self) -> &Self::IdType {
+        &self.id
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[non_exhaustive]
+#[serde(rename_all = "lowercase")]
+pub enum PointOfInterestType {
+    Landmark,
+    Waypoint,
+    Vista,
+    Unlock,
+    #[serde(untagged)]
+    Unknown(String),
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct PointOfInterest {
+    pub id: PointOfInterestId,
+    pub name: Option<String>,
+    #[serde(rename = "type")]
+    pub _type: PointOfInterestType,
+    pub floor: FloorId,
+    pub coord: Coordinates,
+    pub chat_link: String,
+    pub icon: Option<String>,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct GodShrine {
+    pub id: u32,
+    pub name: String,
+    pub name_contested: String,
+    pub icon: String,
+    pub icon_contested: String,
+    pub poi_id: PointOfInterestId,
+    pub coord: Coordinates,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
+}
+
+/// a renown heart
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Task {
+    pub id: TaskId,
+    pub objective: String,
+    pub level: u8,
+    pub coord: Coordinates,
+    pub bounds: Vec<Coordinates>,
+    pub chat_link: String,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
+}
+
+/// a hero point
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct SkillChallenge {
+    pub id: Option<String>,
+    pub coord: Coordinates,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Sector {
+    pub id: SectorId,
+    pub name: Option<String>,
+    pub level: u8,
+    pub coord: Coordinates,
+    pub bounds: Vec<Coordinates>,
+    pub chat_link: String,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Adventure {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub coord: Coordinates,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct MasteryPoint {
+    pub id: u32,
+    pub region: String,
+    pub coord: Coordinates,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Map {
+    pub name: String,
+    pub min_level: u8,
+    pub max_level: u8,
+    pub default_floor: FloorId,
+    pub label_coord: Option<Coordinates>,
+    pub map_rect: Rect,
+    pub continent_rect: Rect,
+    pub points_of_interest: HashMap<PointOfInterestId, PointOfInterest>,
+    #[serde(default)]
+    pub god_shrines: Vec<GodShrine>,
+    pub tasks: HashMap<TaskId, Task>,
+    pub skill_challenges: Vec<SkillChallenge>,
+    pub sectors: HashMap<SectorId, Sector>,
+    pub adventures: Vec<Adventure>,
+    pub mastery_points: Vec<MasteryPoint>,
+    pub id: MapPath,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
+}
+
+impl Endpoint for Map {
+    const AUTHENTICATED: bool = false;
+    const LOCALE: bool = true;
+    const URL: &'static str = "v2/continents/:continent/floors/:floor/regions/:region/maps";
+    const VERSION: &'static str = "2022-07-22T00:00:00.000Z";
+}
+
+impl EndpointWithId for Map {
+    type IdType = MapPath;
+
+    fn parents(id: &Self::IdType) -> Vec<String> {
+        id.parents()
+    }
+
+    fn set_parents(&mut self, id: &Self::IdType) {
+        self.id.parent = id.parent;
+    }
+}
+
+impl BulkEndpoint for Map {
+    const ALL: bool = false;
+
+    fn id(&self) -> &Self::IdType {
+        &self.id
+    }
+}
@@ -2,17 +2,27 @@ use serde::{Deserialize, Serialize};
 
 use crate::{BulkEndpoint, Endpoint, EndpointWithId};
 
-pub type CurrencyId = u16;
+id!(CurrencyId(u16));
 
-#[derive(Clone, PartialEq, Eq, PartialOrd, Debug, Serialize, Deserialize)]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub struct Currency {
     pub id: CurrencyId,
     pub name: String,
     pub description: String,
     pub icon: String,
     pub order: u8,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
 }
 
+partial_ord_without_extra!(Currency {
+    id,
+    name,
+    description,
+    icon,
+    order
+});
+
 impl EndpointWithId for Currency {
     type IdType = CurrencyId;
 }
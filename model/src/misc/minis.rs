@@ -1 +1 @@
-pub type MiniPetId = u64;
+id!(MiniPetId(u64));
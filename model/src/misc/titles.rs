@@ -1 +1 @@
-pub type TitleId = u16;
+id!(TitleId(u16));
@@ -2,14 +2,17 @@ use serde::{Deserialize, Serialize};
 
 use crate::*;
 
-pub type WorldId = u16;
+id!(WorldId(u16));
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum PopulationLevel {
     Medium,
     High,
     VeryHigh,
     Full,
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -17,6 +20,8 @@ pub struct World {
     id: WorldId,
     name: String,
     population: PopulationLevel,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
 }
 
 impl Endpoint for World {
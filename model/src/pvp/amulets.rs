@@ -1 +1 @@
-pub type AmuletId = u16;
+id!(AmuletId(u16));
@@ -0,0 +1,106 @@
+//! ids that point to entities of other endpoints
+
+use std::collections::{BTreeSet, HashMap};
+
+use crate::{
+    items::{
+        recipes::{Recipe, RecipeId},
+        Item, ItemId,
+    },
+    misc::{
+        colors::{Color, ColorId},
+        currencies::{Currency, CurrencyId},
+        worlds::{World, WorldId},
+    },
+};
+
+/// entities that refer to entities of other endpoints by id
+///
+/// only the kinds in [`ReferenceSet`] are collected. Skins and item stats,
+/// e.g. [`Equip::skin`](crate::authenticated::characters::Equip::skin) and
+/// [`Stats::id`](crate::authenticated::characters::Stats::id), are skipped
+/// until their endpoints are modelled.
+pub trait References {
+    /// adds every id this entity refers to
+    fn references(&self, set: &mut ReferenceSet);
+}
+
+/// ids collected by [`References`], grouped by endpoint
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ReferenceSet {
+    pub items: BTreeSet<ItemId>,
+    pub recipes: BTreeSet<RecipeId>,
+    pub colors: BTreeSet<ColorId>,
+    pub currencies: BTreeSet<CurrencyId>,
+    pub worlds: BTreeSet<WorldId>,
+}
+
+impl ReferenceSet {
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+            && self.recipes.is_empty()
+            && self.colors.is_empty()
+            && self.currencies.is_empty()
+            && self.worlds.is_empty()
+    }
+}
+
+/// the entities of a [`ReferenceSet`], by id
+///
+/// ids the api doesn't know are missing
+#[derive(Clone, Debug, Default)]
+pub struct Resolved {
+    pub items: HashMap<ItemId, Item>,
+    pub recipes: HashMap<RecipeId, Recipe>,
+    pub colors: HashMap<ColorId, Color>,
+    pub currencies: HashMap<CurrencyId, Currency>,
+    pub worlds: HashMap<WorldId, World>,
+}
+
+macro_rules! reference {
+    ($($id:ty => $field:ident),*) => {
+        $(
+            impl References for $id {
+                fn references(&self, set: &mut ReferenceSet) {
+                    set.$field.insert(*self);
+                }
+            }
+        )*
+    };
+}
+
+reference!(
+    ItemId => items,
+    RecipeId => recipes,
+    ColorId => colors,
+    CurrencyId => currencies,
+    WorldId => worlds
+);
+
+impl<T: References> References for Option<T> {
+    fn references(&self, set: &mut ReferenceSet) {
+        if let Some(x) = self {
+            x.references(set);
+        }
+    }
+}
+
+impl<T: References> References for [T] {
+    fn references(&self, set: &mut ReferenceSet) {
+        for x in self {
+            x.references(set);
+        }
+    }
+}
+
+impl<T: References> References for Vec<T> {
+    fn references(&self, set: &mut ReferenceSet) {
+        self.as_slice().references(set);
+    }
+}
+
+impl<T: References + ?Sized> References for &T {
+    fn references(&self, set: &mut ReferenceSet) {
+        (**self).references(set);
+    }
+}
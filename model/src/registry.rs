@@ -0,0 +1,200 @@
+//! every endpoint of this crate, for tooling that works without naming the
+//! types
+
+use std::any::type_name;
+
+use crate::{
+    authenticated::{
+        account::{bank::Bank, materials::AccountMaterials, v2024_07_20, wallet::Wallet, Account},
+        characters::{
+            Backstory, Character, Core, Crafting, Equipment, Inventory, Recipes, Training,
+        },
+        Permissions, Tokeninfo,
+    },
+    game_mechanics::{skills::Skill, specializations::Specialization},
+    items::{
+        recipes::{Recipe, RecipeSearch},
+        Item,
+    },
+    misc::{
+        build::Build,
+        colors::Color,
+        continents::{Continent, Floor, Map, Region},
+        currencies::Currency,
+        worlds::World,
+    },
+    tradingpost::{Listings, Prices},
+    BulkEndpoint, Endpoint, EndpointWithId, FixedEndpoint, QueryEndpoint,
+};
+
+#[derive(Clone, Copy, Debug)]
+pub struct EndpointInfo {
+    /// see [`Endpoint::URL`]
+    pub url: &'static str,
+    pub version: &'static str,
+    pub authenticated: bool,
+    pub locale: bool,
+    pub permissions: &'static [Permissions],
+    /// the rust type, e.g. `gw2lib_model::items::Item`
+    pub type_name: &'static str,
+    pub kind: EndpointKind,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum EndpointKind {
+    Fixed,
+    Query,
+    WithId {
+        id_type: &'static str,
+        /// see [`EndpointWithId::format_url`]
+        format_url: fn(&str) -> String,
+    },
+    Bulk {
+        id_type: &'static str,
+        /// see [`EndpointWithId::format_url`]
+        format_url: fn(&str) -> String,
+        /// whether `ids=all` is supported
+        all: bool,
+    },
+}
+
+impl EndpointInfo {
+    fn new<T: Endpoint>(kind: EndpointKind) -> Self {
+        Self {
+            url: T::URL,
+            version: T::VERSION,
+            authenticated: T::AUTHENTICATED,
+            locale: T::LOCALE,
+            permissions: T::PERMISSIONS,
+            type_name: type_name::<T>(),
+            kind,
+        }
+    }
+
+    pub fn fixed<T: FixedEndpoint>() -> Self {
+        Self::new::<T>(EndpointKind::Fixed)
+    }
+
+    pub fn query<T: QueryEndpoint>() -> Self {
+        Self::new::<T>(EndpointKind::Query)
+    }
+
+    pub fn with_id<T: EndpointWithId>() -> Self {
+        Self::new::<T>(EndpointKind::WithId {
+            id_type: type_name::<T::IdType>(),
+            format_url: T::format_url,
+        })
+    }
+
+    pub fn bulk<T: BulkEndpoint>() -> Self {
+        Self::new::<T>(EndpointKind::Bulk {
+            id_type: type_name::<T::IdType>(),
+            format_url: T::format_url,
+            all: T::ALL,
+        })
+    }
+
+    /// the url templates of this endpoint, ids and parents are `:placeholders`
+    pub fn templates(&self) -> Vec<String> {
+        match self.kind {
+            EndpointKind::Fixed | EndpointKind::Query => vec![self.url.to_string()],
+            EndpointKind::WithId { format_url, .. } | EndpointKind::Bulk { format_url, .. } => {
+                vec![self.url.to_string(), format_url(":id")]
+            }
+        }
+    }
+}
+
+/// all endpoints, new ones need to be added here
+pub fn endpoints() -> Vec<EndpointInfo> {
+    vec![
+        EndpointInfo::fixed::<Tokeninfo>(),
+        EndpointInfo::fixed::<Account>(),
+        EndpointInfo::fixed::<v2024_07_20::Account>(),
+        EndpointInfo::fixed::<Bank>(),
+        EndpointInfo::fixed::<AccountMaterials>(),
+        EndpointInfo::fixed::<Wallet>(),
+        EndpointInfo::bulk::<Character>(),
+        EndpointInfo::with_id::<Core>(),
+        EndpointInfo::with_id::<Backstory>(),
+        EndpointInfo::with_id::<Crafting>(),
+        EndpointInfo::with_id::<Equipment>(),
+        EndpointInfo::with_id::<Inventory>(),
+        EndpointInfo::with_id::<Recipes>(),
+        EndpointInfo::with_id::<Training>(),
+        EndpointInfo::bulk::<Skill>(),
+        EndpointInfo::bulk::<Specialization>(),
+        EndpointInfo::bulk::<Item>(),
+        EndpointInfo::bulk::<Recipe>(),
+        EndpointInfo::query::<RecipeSearch>(),
+        EndpointInfo::fixed::<Build>(),
+        EndpointInfo::bulk::<Color>(),
+        EndpointInfo::bulk::<Continent>(),
+        EndpointInfo::bulk::<Floor>(),
+        EndpointInfo::bulk::<Region>(),
+        EndpointInfo::bulk::<Map>(),
+        EndpointInfo::bulk::<Currency>(),
+        EndpointInfo::bulk::<World>(),
+        EndpointInfo::bulk::<Listings>(),
+        EndpointInfo::bulk::<Prices>(),
+    ]
+}
+
+/// the endpoint serving `path`, e.g. `v2/characters/My Character/core`
+///
+/// the query string is ignored. Templates with more literal segments win, so
+/// `v2/recipes/search` isn't mistaken for a recipe id. Schema versions of the
+/// same endpoint, like the two models of `v2/account`, share their templates;
+/// the first one in [`endpoints`] wins, see [`find_versioned`] to choose.
+pub fn find(path: &str) -> Option<EndpointInfo> {
+    candidates(path).into_iter().next()
+}
+
+/// [`find`], preferring the newest model at or before the schema `version`
+///
+/// falls back to [`find`] if all models are newer
+pub fn find_versioned(path: &str, version: &str) -> Option<EndpointInfo> {
+    let candidates = candidates(path);
+    candidates
+        .iter()
+        .filter(|info| info.version <= version)
+        .max_by_key(|info| info.version)
+        .or(candidates.first())
+        .copied()
+}
+
+/// the endpoints with the most literal segments matching `path`, in order
+fn candidates(path: &str) -> Vec<EndpointInfo> {
+    let path = path.split_once('?').map_or(path, |(path, _)| path);
+    let path = path.trim_matches('/');
+    let segments: Vec<_> = path.split('/').collect();
+
+    let mut best = 0;
+    let mut candidates = Vec::new();
+    for info in endpoints() {
+        for template in info.templates() {
+            let template: Vec<_> = template.split('/').collect();
+            if template.len() != segments.len() {
+                continue;
+            }
+            let mut literals = 0;
+            let matches = template.iter().zip(&segments).all(|(t, s)| {
+                if t.starts_with(':') {
+                    true
+                } else {
+                    literals += 1;
+                    t == s
+                }
+            });
+            if !matches || literals < best {
+                continue;
+            }
+            if literals > best {
+                best = literals;
+                candidates.clear();
+            }
+            candidates.push(info);
+        }
+    }
+    candidates
+}
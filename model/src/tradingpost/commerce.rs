@@ -1,12 +1,18 @@
 use serde::{Deserialize, Serialize};
 
-use crate::{items::ItemId, BulkEndpoint, Endpoint, EndpointWithId};
+use crate::{
+    items::ItemId,
+    references::{ReferenceSet, References},
+    BulkEndpoint, Endpoint, EndpointWithId,
+};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ListingDetails {
     pub listings: u64,
     pub unit_price: u64,
     pub quantity: u64,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -14,6 +20,14 @@ pub struct Listings {
     pub id: ItemId,
     pub buys: Vec<ListingDetails>,
     pub sells: Vec<ListingDetails>,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
+}
+
+impl References for Listings {
+    fn references(&self, set: &mut ReferenceSet) {
+        self.id.references(set);
+    }
 }
 
 impl EndpointWithId for Listings {
@@ -38,6 +52,8 @@ impl BulkEndpoint for Listings {
 pub struct PriceDetails {
     pub unit_price: u64,
     pub quantity: u64,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -46,6 +62,14 @@ pub struct Prices {
     pub whitelisted: bool,
     pub buys: PriceDetails,
     pub sells: PriceDetails,
+    #[serde(flatten)]
+    pub extra: crate::Extra,
+}
+
+impl References for Prices {
+    fn references(&self, set: &mut ReferenceSet) {
+        self.id.references(set);
+    }
 }
 
 impl EndpointWithId for Prices {
@@ -1 +1 @@
-pub type AbilityId = u32;
+id!(AbilityId(u32));
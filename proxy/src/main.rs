@@ -1,5 +1,3 @@
-use std::{time::Duration};
-
 use tokio::sync::Mutex;
 use actix_web::{http::header::HeaderName, web, HttpRequest, HttpResponse, Responder};
 use gw2lib::rate_limit::{BucketRateLimiter, RateLimiter};
@@ -40,7 +38,7 @@ async fn index(req: HttpRequest) -> impl Responder {
         .app_data::<web::Data<Mutex<BucketRateLimiter>>>()
         .unwrap();
     let dur = { rate.lock().await.take(1).await.unwrap() };
-    tokio::time::sleep(Duration::from_secs(dur)).await;
+    tokio::time::sleep(dur).await;
     println!("{} - {:?}", spoof.url(), spoof.headers());
     let res = reqwest::Client::default().execute(spoof).await;
     match res {